@@ -41,6 +41,26 @@
 //! coordinates as input, allowing you to use the most convenient coordinate system for your
 //! particular application.
 //!
+//! The `LogicalRect` and `PhysicalRect` types pair a position with a size, and provide the
+//! usual geometry operations (intersection, union, containment) so they don't have to be
+//! reimplemented by every backend.
+//!
+//! `LogicalInsets` and `PhysicalInsets` hold how far each edge of a rectangle is moved in, such as
+//! safe area insets or window frame extents, and can be taken off or added to rectangles and sizes
+//! to get from a window's outer size to its inner size and back.
+//!
+//! [`Transform`] maps physical positions, sizes and rectangles onto a rotated or flipped surface,
+//! e.g. for outputs using `wl_output.transform`.
+//!
+//! Scalar lengths, such as stroke widths, corner radii and scroll distances, have their own
+//! `LogicalUnit` and `PhysicalUnit` types, so that they get scaled like everything else instead of
+//! being passed around as bare numbers.
+//!
+//! The usual arithmetic operators are implemented where they make sense: subtracting two positions
+//! gives a `LogicalDelta` or `PhysicalDelta`, which can be added to positions and rectangles to move
+//! them, and sizes and deltas can be multiplied or divided by a scalar. Logical and physical values
+//! can never be mixed; convert one into the other first.
+//!
 //! Winit's position and size types types are generic over their exact pixel type, `P`, to allow the
 //! API to have integer precision where appropriate (e.g. most window manipulation functions) and
 //! floating precision when necessary (e.g. logical sizes for fractional scale factors and touch
@@ -50,6 +70,38 @@
 //! rounding properly. Note that precision loss will still occur when rounding from a float to an
 //! int, although rounding lessens the problem.
 //!
+//! Conversions take anything convertible into a [`ScaleFactor2D`], which is usually a single
+//! [`ScaleFactor`] (or a bare `f64`) applied to both axes. Separate horizontal and vertical factors
+//! can be used for the rare setups where pixels aren't square.
+//!
+//! Every geometry type here also implements the [`ToPhysical`] and [`ToLogical`] traits, so that generic
+//! code can accept anything convertible to physical or logical pixels. Lengths have no direction,
+//! so through these traits they're scaled by the horizontal factor.
+//!
+//! The two-dimensional types are aliases of the unit-tagged types in the [`typed`] module, using
+//! its `Logical` and `Physical` units. Applications with coordinate spaces of their own can define
+//! extra units there, and convert between them with a typed [`Scale`].
+//!
+//! Since 0.2, this means that positions, deltas, sizes and insets have a `unit` field marking
+//! their unit, which struct literals have to fill in with `PhantomData` and patterns have to skip
+//! with a `..`. Their `new` functions (or `From` tuples) are usually simpler, e.g.
+//! `LogicalSize::new(1.0, 2.0)` rather than
+//! `LogicalSize { width: 1.0, height: 2.0, unit: PhantomData }`. Rectangles only hold a position
+//! and a size, so they aren't affected.
+//!
+//! ### Fallible conversions
+//!
+//! The conversions above panic if given an `f64` that isn't a valid scale factor, and saturate
+//! results that don't fit in the pixel type they're converted to. Each of them has a `try_`
+//! counterpart which takes the same scale factors, through [`TryIntoScaleFactor2D`] (or
+//! [`TryIntoScaleFactor`] for lengths), and returns an error instead:
+//!
+//! - [`ErrorType::InvalidScaleFactor`] if the scale factor is an `f64` that's zero, negative,
+//!   subnormal, infinite or NaN, as checked by [`validate_scale_factor`]. [`ScaleFactor`]s and the
+//!   other scale factor types are always valid.
+//! - [`ErrorType::InvalidPixelValue`] if a converted component isn't finite or, once rounded,
+//!   doesn't fit in the target pixel type, as checked by [`Pixel::try_from_f64`].
+//!
 //! ### Events
 //!
 //! Winit will dispatch a [`ScaleFactorChanged`] event whenever a window's scale factor has changed.
@@ -76,13 +128,17 @@
 //!   + Use the value in the `WINIT_X11_SCALE_FACTOR` environment variable, if present.
 //!   + If not present, use the value set in `Xft.dpi` in Xresources.
 //!   + Otherwise, calcuate the scale factor based on the millimeter monitor dimensions provided by XRandR.
+//!     See [`scale_factor_from_mm`].
 //!   + If the system doesn't support XRandR, we use the values provided by Xinerama/Xlib.
 //!
 //!   If `WINIT_X11_SCALE_FACTOR` is set to `randr`, it'll ignore the `Xft.dpi` field and use the
 //!   XRandR scaling method. Generally speaking, you should try to configure the standard system
 //!   variables to do what you want before resorting to `WINIT_X11_SCALE_FACTOR`.
-//! - **Wayland:** On Wayland, scale factors are set per-screen by the server, and are always
-//!   integers (most often 1 or 2).
+//!
+//!   These rules are implemented by [`resolve_x11_scale_factor`].
+//! - **Wayland:** On Wayland, scale factors are set per-screen by the server. Compositors
+//!   supporting `wp_fractional_scale_v1` send scales in 120ths (e.g. 1.25 or 1.5), which are
+//!   represented exactly by [`FractionalScale`]; others only use integers (most often 1 or 2).
 //! - **iOS:** Scale factors are set by Apple to the value that best suits the device, and range
 //!   from `1.0` to `3.0`. See [this article][apple_1] and [this article][apple_2] for more
 //!   information.
@@ -96,411 +152,3183 @@
 //! [apple_1]: https://developer.apple.com/library/archive/documentation/DeviceInformation/Reference/iOSDeviceCompatibility/Displays/Displays.html
 //! [apple_2]: https://developer.apple.com/design/human-interface-guidelines/macos/icons-and-images/image-size-and-resolution/
 //! [android_1]: https://developer.android.com/training/multiscreen/screendensities
+//! [`scale_factor_from_mm`]: fn.scale_factor_from_mm.html
+//! [`FractionalScale`]: struct.FractionalScale.html
+//! [`ScaleFactor`]: struct.ScaleFactor.html
+//! [`ScaleFactor2D`]: struct.ScaleFactor2D.html
+//! [`TryIntoScaleFactor2D`]: trait.TryIntoScaleFactor2D.html
+//! [`TryIntoScaleFactor`]: trait.TryIntoScaleFactor.html
+//! [`ErrorType::InvalidScaleFactor`]: ../error/enum.ErrorType.html#variant.InvalidScaleFactor
+//! [`ErrorType::InvalidPixelValue`]: ../error/enum.ErrorType.html#variant.InvalidPixelValue
+//! [`validate_scale_factor`]: fn.validate_scale_factor.html
+//! [`Pixel::try_from_f64`]: trait.Pixel.html#method.try_from_f64
+//! [`ToPhysical`]: trait.ToPhysical.html
+//! [`ToLogical`]: trait.ToLogical.html
+//! [`typed`]: typed/index.html
+//! [`Transform`]: enum.Transform.html
+//! [`Scale`]: typed/struct.Scale.html
+//! [`resolve_x11_scale_factor`]: fn.resolve_x11_scale_factor.html
 //! [`ScaleFactorChanged`]: https://docs.rs/winit/0.20.0/winit/event/enum.WindowEvent.html#variant.ScaleFactorChanged
 
+pub mod typed;
+
+use crate::error::{Error, ErrorType};
+
+use self::typed::{Logical, Physical};
+
+use std::{fmt, ops};
+
 pub trait Pixel: Copy + Into<f64> {
     fn from_f64(f: f64) -> Self;
-    fn cast<P: Pixel>(self) -> P {
-        P::from_f64(self.into())
+
+    /// Like [`from_f64`], but returns an error if `f` isn't finite or, once rounded, doesn't fit in
+    /// `Self`.
+    ///
+    /// The default implementation only checks that `f` is finite.
+    ///
+    /// [`from_f64`]: #tymethod.from_f64
+    fn try_from_f64(f: f64) -> Result<Self, Error> {
+        if f.is_finite() {
+            Ok(Self::from_f64(f))
+        } else {
+            Err(lmake_error!(ErrorType::InvalidPixelValue(f)))
+        }
     }
-}
 
-impl Pixel for u8 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as u8
+    /// Like [`from_f64`], but clamps `f` to the range of `Self`. NaN becomes `0`.
+    ///
+    /// The default implementation is [`from_f64`], which already saturates for the integer types.
+    ///
+    /// [`from_f64`]: #tymethod.from_f64
+    fn saturating_from_f64(f: f64) -> Self {
+        Self::from_f64(f)
     }
-}
-impl Pixel for u16 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as u16
+
+    /// Like [`from_f64`], but rounds `f` using `rounding`, whatever the type of `Self`.
+    ///
+    /// [`from_f64`]: #tymethod.from_f64
+    #[inline]
+    fn from_f64_with(f: f64, rounding: Rounding) -> Self {
+        Self::from_f64(rounding.apply(f))
     }
-}
-impl Pixel for u32 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as u32
+
+    fn cast<P: Pixel>(self) -> P {
+        P::from_f64(self.into())
     }
-}
-impl Pixel for i8 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as i8
+
+    #[inline]
+    fn cast_with<P: Pixel>(self, rounding: Rounding) -> P {
+        P::from_f64_with(self.into(), rounding)
     }
-}
-impl Pixel for i16 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as i16
+
+    #[inline]
+    fn try_cast<P: Pixel>(self) -> Result<P, Error> {
+        P::try_from_f64(self.into())
     }
-}
-impl Pixel for i32 {
-    fn from_f64(f: f64) -> Self {
-        f.round() as i32
+
+    #[inline]
+    fn saturating_cast<P: Pixel>(self) -> P {
+        P::saturating_from_f64(self.into())
     }
 }
+
+macro_rules! impl_integer_pixel {
+    ($($t:ident),*) => {$(
+        impl Pixel for $t {
+            fn from_f64(f: f64) -> Self {
+                f.round() as $t
+            }
+
+            fn try_from_f64(f: f64) -> Result<Self, Error> {
+                let rounded = f.round();
+                if rounded >= $t::MIN as f64 && rounded <= $t::MAX as f64 {
+                    Ok(rounded as $t)
+                } else {
+                    Err(lmake_error!(ErrorType::InvalidPixelValue(f)))
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer_pixel!(u8, u16, u32, i8, i16, i32);
+
 impl Pixel for f32 {
     fn from_f64(f: f64) -> Self {
         f as f32
     }
+
+    fn try_from_f64(f: f64) -> Result<Self, Error> {
+        if f.is_finite() && f.abs() <= f32::MAX as f64 {
+            Ok(f as f32)
+        } else {
+            Err(lmake_error!(ErrorType::InvalidPixelValue(f)))
+        }
+    }
+
+    fn saturating_from_f64(f: f64) -> Self {
+        if f.is_nan() {
+            0.0
+        } else {
+            f.clamp(f32::MIN as f64, f32::MAX as f64) as f32
+        }
+    }
 }
 impl Pixel for f64 {
     fn from_f64(f: f64) -> Self {
         f
     }
-}
 
-/// Checks that the scale factor is a normal positive `f64`.
-///
-/// All functions that take a scale factor assert that this will return `true`. If you're sourcing scale factors from
-/// anywhere other than winit, it's recommended to validate them using this function before passing them to winit;
-/// otherwise, you risk panics.
-#[inline]
-pub fn validate_scale_factor(dpi_factor: f64) -> bool {
-    dpi_factor.is_sign_positive() && dpi_factor.is_normal()
+    fn saturating_from_f64(f: f64) -> Self {
+        if f.is_nan() {
+            0.0
+        } else {
+            f.clamp(f64::MIN, f64::MAX)
+        }
+    }
 }
 
-/// A position represented in logical pixels.
+/// How to round fractional pixel values.
 ///
-/// The position is stored as floats, so please be careful. Casting floats to integers truncates the
-/// fractional part, which can cause noticable issues. To help with that, an `Into<(i32, i32)>`
-/// implementation is provided which does the rounding for you.
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// [`Pixel::cast`] and the `cast`/`to_physical`/`to_logical` functions always use [`Round`], and
+/// only round when casting to an integer type. The `_with` variants let you pick the mode, and
+/// apply it whatever the target type is.
+///
+/// [`Pixel::cast`]: trait.Pixel.html#method.cast
+/// [`Round`]: #variant.Round
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub struct LogicalPosition<P> {
-    pub x: P,
-    pub y: P,
+pub enum Rounding {
+    /// Round to the nearest integer, with halfway values rounded away from zero.
+    Round,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round to the nearest integer, with halfway values rounded to the nearest even integer.
+    RoundHalfEven,
+    /// Round so that the result covers at least the original area: positions and the top-left
+    /// edges of rectangles are floored, while sizes and the bottom-right edges of rectangles are
+    /// ceiled. Lone values are ceiled.
+    ///
+    /// This guarantees that a converted damage or scissor rectangle covers the whole logical area,
+    /// at the cost of possibly covering an extra pixel on each side.
+    Outward,
 }
 
-impl<P> LogicalPosition<P> {
+impl Default for Rounding {
     #[inline]
-    pub const fn new(x: P, y: P) -> Self {
-        LogicalPosition { x, y }
+    fn default() -> Self {
+        Rounding::Round
     }
 }
 
-impl<P: Pixel> LogicalPosition<P> {
+impl Rounding {
     #[inline]
-    pub fn from_physical<T: Into<PhysicalPosition<X>>, X: Pixel>(
-        physical: T,
-        dpi_factor: f64,
-    ) -> Self {
-        physical.into().to_logical(dpi_factor)
+    pub fn apply(self, f: f64) -> f64 {
+        match self {
+            Rounding::Round => f.round(),
+            Rounding::Floor => f.floor(),
+            Rounding::Ceil | Rounding::Outward => f.ceil(),
+            Rounding::RoundHalfEven => {
+                let rounded = f.round();
+                if (f - f.trunc()).abs() == 0.5 {
+                    2.0 * (f / 2.0).round()
+                } else {
+                    rounded
+                }
+            }
+        }
     }
 
+    /// The mode to use for positions and the top-left edges of rectangles.
     #[inline]
-    pub fn to_physical<X: Pixel>(&self, dpi_factor: f64) -> PhysicalPosition<X> {
-        assert!(validate_scale_factor(dpi_factor));
-        let x = self.x.into() * dpi_factor;
-        let y = self.y.into() * dpi_factor;
-        PhysicalPosition::new(x, y).cast()
+    fn lower(self) -> Self {
+        match self {
+            Rounding::Outward => Rounding::Floor,
+            rounding => rounding,
+        }
     }
 
+    /// The mode to use for sizes and the bottom-right edges of rectangles.
     #[inline]
-    pub fn cast<X: Pixel>(&self) -> LogicalPosition<X> {
-        LogicalPosition {
-            x: self.x.cast(),
-            y: self.y.cast(),
+    fn upper(self) -> Self {
+        match self {
+            Rounding::Outward => Rounding::Ceil,
+            rounding => rounding,
         }
     }
 }
 
-impl<P: Pixel, X: Pixel> From<(X, X)> for LogicalPosition<P> {
-    fn from((x, y): (X, X)) -> LogicalPosition<P> {
-        LogicalPosition::new(x.cast(), y.cast())
-    }
+/// Checks that the scale factor is a normal positive `f64`.
+///
+/// All functions that take a scale factor as an `f64` assert that this will return `true`. If
+/// you're sourcing scale factors from anywhere other than winit, it's recommended to validate them
+/// with [`ScaleFactor::new`] before passing them to winit; otherwise, you risk panics.
+///
+/// [`ScaleFactor::new`]: struct.ScaleFactor.html#method.new
+#[inline]
+pub fn validate_scale_factor(dpi_factor: f64) -> bool {
+    dpi_factor.is_sign_positive() && dpi_factor.is_normal()
 }
 
-impl<P: Pixel, X: Pixel> Into<(X, X)> for LogicalPosition<P> {
-    fn into(self: Self) -> (X, X) {
-        (self.x.cast(), self.y.cast())
-    }
+/// A scale factor which is known to be valid.
+///
+/// The only ways to get one are [`ScaleFactor::new`] and [`ScaleFactor::from_ratio`], which return
+/// an error if the value isn't valid, and the `From<f64>` implementation, which panics instead. The
+/// latter exists so that code passing bare `f64`s to the conversion functions keeps working, but
+/// new code should prefer the former.
+///
+/// Scale factors created from a ratio, as well as whole-number ones, are exact: conversions
+/// multiply by the numerator and divide by the denominator rather than going through an
+/// approximated `f64`, so a logical value is exact whenever it can be represented, and whole-number
+/// scales only ever multiply or divide once. Converting an integer physical value to `f64` logical
+/// pixels and back always gives back the original value.
+///
+/// [`ScaleFactor::new`]: struct.ScaleFactor.html#method.new
+/// [`ScaleFactor::from_ratio`]: struct.ScaleFactor.html#method.from_ratio
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(Serialize, Deserialize),
+    serde(try_from = "RawScaleFactor", into = "RawScaleFactor")
+)]
+pub struct ScaleFactor {
+    factor: f64,
+    /// The numerator and denominator, in lowest terms, if the factor is exactly known.
+    ratio: Option<(u32, u32)>,
 }
 
-impl<P: Pixel, X: Pixel> From<[X; 2]> for LogicalPosition<P> {
-    fn from([x, y]: [X; 2]) -> LogicalPosition<P> {
-        LogicalPosition::new(x.cast(), y.cast())
+#[inline]
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
     }
+    a
 }
 
-impl<P: Pixel, X: Pixel> Into<[X; 2]> for LogicalPosition<P> {
-    fn into(self: Self) -> [X; 2] {
-        [self.x.cast(), self.y.cast()]
+impl ScaleFactor {
+    /// A scale factor of `1.0`, where logical and physical pixels are the same.
+    pub const IDENTITY: ScaleFactor = ScaleFactor {
+        factor: 1.0,
+        ratio: Some((1, 1)),
+    };
+
+    #[inline]
+    pub fn new(dpi_factor: f64) -> Result<Self, Error> {
+        if validate_scale_factor(dpi_factor) {
+            Ok(ScaleFactor::from_valid(dpi_factor))
+        } else {
+            Err(lmake_error!(ErrorType::InvalidScaleFactor(dpi_factor)))
+        }
     }
-}
 
-/// A position represented in physical pixels.
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub struct PhysicalPosition<P> {
-    pub x: P,
-    pub y: P,
-}
+    /// Creates the exact scale factor `numerator / denominator`. Returns an error if either is `0`.
+    #[inline]
+    pub fn from_ratio(numerator: u32, denominator: u32) -> Result<Self, Error> {
+        if numerator == 0 || denominator == 0 {
+            let dpi_factor = if denominator == 0 { f64::NAN } else { 0.0 };
+            return Err(lmake_error!(ErrorType::InvalidScaleFactor(dpi_factor)));
+        }
+        let divisor = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        Ok(ScaleFactor {
+            factor: numerator as f64 / denominator as f64,
+            ratio: Some((numerator, denominator)),
+        })
+    }
 
-impl<P> PhysicalPosition<P> {
+    /// Whole-number factors are always treated as exact.
     #[inline]
-    pub const fn new(x: P, y: P) -> Self {
-        PhysicalPosition { x, y }
+    fn from_valid(dpi_factor: f64) -> Self {
+        let ratio = if dpi_factor.fract() == 0.0 && dpi_factor <= u32::MAX as f64 {
+            Some((dpi_factor as u32, 1))
+        } else {
+            None
+        };
+        ScaleFactor {
+            factor: dpi_factor,
+            ratio,
+        }
     }
-}
 
-impl<P: Pixel> PhysicalPosition<P> {
     #[inline]
-    pub fn from_logical<T: Into<LogicalPosition<X>>, X: Pixel>(
-        logical: T,
-        dpi_factor: f64,
-    ) -> Self {
-        logical.into().to_physical(dpi_factor)
+    pub fn get(self) -> f64 {
+        self.factor
     }
 
+    /// Returns the numerator and denominator of the factor, in lowest terms, if it's exact.
     #[inline]
-    pub fn to_logical<X: Pixel>(&self, dpi_factor: f64) -> LogicalPosition<X> {
-        assert!(validate_scale_factor(dpi_factor));
-        let x = self.x.into() / dpi_factor;
-        let y = self.y.into() / dpi_factor;
-        LogicalPosition::new(x, y).cast()
+    pub fn ratio(self) -> Option<(u32, u32)> {
+        self.ratio
     }
 
+    /// Converts a logical value to physical pixels.
     #[inline]
-    pub fn cast<X: Pixel>(&self) -> PhysicalPosition<X> {
-        PhysicalPosition {
-            x: self.x.cast(),
-            y: self.y.cast(),
+    fn scale(self, value: f64) -> f64 {
+        match self.ratio {
+            Some((numerator, 1)) => value * numerator as f64,
+            Some((numerator, denominator)) => value * numerator as f64 / denominator as f64,
+            None => value * self.factor,
         }
     }
+
+    /// Converts a physical value to logical pixels.
+    #[inline]
+    fn unscale(self, value: f64) -> f64 {
+        match self.ratio {
+            Some((numerator, 1)) => value / numerator as f64,
+            Some((numerator, denominator)) => value * denominator as f64 / numerator as f64,
+            None => value / self.factor,
+        }
+    }
+
+    /// The factor undoing this one.
+    #[inline]
+    fn recip(self) -> ScaleFactor {
+        match self.ratio {
+            Some((numerator, denominator)) => ScaleFactor {
+                factor: denominator as f64 / numerator as f64,
+                ratio: Some((denominator, numerator)),
+            },
+            None => ScaleFactor::from_valid(1.0 / self.factor),
+        }
+    }
+
+    /// The factor applying this one, then `next`. It stays exact if both are, unless the reduced
+    /// ratio doesn't fit in a `u32`.
+    fn then(self, next: ScaleFactor) -> ScaleFactor {
+        if let (Some((n1, d1)), Some((n2, d2))) = (self.ratio, next.ratio) {
+            let (a, b) = (gcd(n1, d2), gcd(n2, d1));
+            let numerator = (n1 / a) as u64 * (n2 / b) as u64;
+            let denominator = (d1 / b) as u64 * (d2 / a) as u64;
+            if numerator <= u32::MAX as u64 && denominator <= u32::MAX as u64 {
+                return ScaleFactor {
+                    factor: numerator as f64 / denominator as f64,
+                    ratio: Some((numerator as u32, denominator as u32)),
+                };
+            }
+        }
+        ScaleFactor::from_valid(self.factor * next.factor)
+    }
+}
+
+impl Default for ScaleFactor {
+    #[inline]
+    fn default() -> Self {
+        ScaleFactor::IDENTITY
+    }
+}
+
+/// Scale factors are compared by value, whether or not they're exact.
+impl PartialEq for ScaleFactor {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.factor == other.factor
+    }
 }
 
-impl<P: Pixel, X: Pixel> From<(X, X)> for PhysicalPosition<P> {
-    fn from((x, y): (X, X)) -> PhysicalPosition<P> {
-        PhysicalPosition::new(x.cast(), y.cast())
+impl PartialOrd for ScaleFactor {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.factor.partial_cmp(&other.factor)
     }
 }
 
-impl<P: Pixel, X: Pixel> Into<(X, X)> for PhysicalPosition<P> {
-    fn into(self: Self) -> (X, X) {
-        (self.x.cast(), self.y.cast())
+impl From<f64> for ScaleFactor {
+    /// Panics if `dpi_factor` doesn't pass [`validate_scale_factor`].
+    ///
+    /// [`validate_scale_factor`]: fn.validate_scale_factor.html
+    #[inline]
+    fn from(dpi_factor: f64) -> Self {
+        assert!(validate_scale_factor(dpi_factor));
+        ScaleFactor::from_valid(dpi_factor)
     }
 }
 
-impl<P: Pixel, X: Pixel> From<[X; 2]> for PhysicalPosition<P> {
-    fn from([x, y]: [X; 2]) -> PhysicalPosition<P> {
-        PhysicalPosition::new(x.cast(), y.cast())
+impl From<ScaleFactor> for f64 {
+    #[inline]
+    fn from(dpi_factor: ScaleFactor) -> Self {
+        dpi_factor.factor
     }
 }
 
-impl<P: Pixel, X: Pixel> Into<[X; 2]> for PhysicalPosition<P> {
-    fn into(self: Self) -> [X; 2] {
-        [self.x.cast(), self.y.cast()]
+impl fmt::Display for ScaleFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ratio {
+            Some((numerator, denominator)) if denominator != 1 => {
+                write!(f, "{}/{}", numerator, denominator)
+            }
+            _ => self.factor.fmt(f),
+        }
     }
 }
 
-/// A size represented in logical pixels.
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// A pair of scale factors for the horizontal and vertical axes.
+///
+/// Every conversion accepts one of these, so that setups where pixels aren't square (e.g. X11
+/// screens reporting non-square physical sizes, or web pages with CSS transforms) can be handled.
+/// Everything convertible into a [`ScaleFactor`] converts into a uniform `ScaleFactor2D`, which is
+/// what's used almost everywhere.
+///
+/// [`ScaleFactor`]: struct.ScaleFactor.html
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub struct LogicalSize<P> {
-    pub width: P,
-    pub height: P,
+pub struct ScaleFactor2D {
+    pub x: ScaleFactor,
+    pub y: ScaleFactor,
 }
 
-impl<P> LogicalSize<P> {
+impl ScaleFactor2D {
+    /// Scale factors of `1.0` on both axes.
+    pub const IDENTITY: ScaleFactor2D = ScaleFactor2D::uniform(ScaleFactor::IDENTITY);
+
     #[inline]
-    pub const fn new(width: P, height: P) -> Self {
-        LogicalSize { width, height }
+    pub const fn new(x: ScaleFactor, y: ScaleFactor) -> Self {
+        ScaleFactor2D { x, y }
     }
-}
 
-impl<P: Pixel> LogicalSize<P> {
+    /// The same scale factor on both axes.
     #[inline]
-    pub fn from_physical<T: Into<PhysicalSize<X>>, X: Pixel>(physical: T, dpi_factor: f64) -> Self {
-        physical.into().to_logical(dpi_factor)
+    pub const fn uniform(dpi_factor: ScaleFactor) -> Self {
+        ScaleFactor2D::new(dpi_factor, dpi_factor)
     }
 
+    /// Like [`new`], but validates both factors with [`ScaleFactor::new`].
+    ///
+    /// [`new`]: #method.new
+    /// [`ScaleFactor::new`]: struct.ScaleFactor.html#method.new
     #[inline]
-    pub fn to_physical<X: Pixel>(&self, dpi_factor: f64) -> PhysicalSize<X> {
-        assert!(validate_scale_factor(dpi_factor));
-        let width = self.width.into() * dpi_factor;
-        let height = self.height.into() * dpi_factor;
-        PhysicalSize::new(width, height).cast()
+    pub fn try_new(x: f64, y: f64) -> Result<Self, Error> {
+        Ok(ScaleFactor2D::new(
+            ScaleFactor::new(x)?,
+            ScaleFactor::new(y)?,
+        ))
     }
 
     #[inline]
-    pub fn cast<X: Pixel>(&self) -> LogicalSize<X> {
-        LogicalSize {
-            width: self.width.cast(),
-            height: self.height.cast(),
-        }
+    pub fn is_uniform(&self) -> bool {
+        self.x == self.y
     }
 }
 
-impl<P: Pixel, X: Pixel> From<(X, X)> for LogicalSize<P> {
-    fn from((x, y): (X, X)) -> LogicalSize<P> {
-        LogicalSize::new(x.cast(), y.cast())
+impl From<ScaleFactor> for ScaleFactor2D {
+    #[inline]
+    fn from(dpi_factor: ScaleFactor) -> Self {
+        ScaleFactor2D::uniform(dpi_factor)
     }
 }
 
-impl<P: Pixel, X: Pixel> Into<(X, X)> for LogicalSize<P> {
-    fn into(self: LogicalSize<P>) -> (X, X) {
-        (self.width.cast(), self.height.cast())
+impl From<f64> for ScaleFactor2D {
+    /// Panics if `dpi_factor` doesn't pass [`validate_scale_factor`].
+    ///
+    /// [`validate_scale_factor`]: fn.validate_scale_factor.html
+    #[inline]
+    fn from(dpi_factor: f64) -> Self {
+        ScaleFactor2D::uniform(dpi_factor.into())
     }
 }
 
-impl<P: Pixel, X: Pixel> From<[X; 2]> for LogicalSize<P> {
-    fn from([x, y]: [X; 2]) -> LogicalSize<P> {
-        LogicalSize::new(x.cast(), y.cast())
+impl From<FractionalScale> for ScaleFactor2D {
+    #[inline]
+    fn from(scale: FractionalScale) -> Self {
+        ScaleFactor2D::uniform(scale.into())
     }
 }
 
-impl<P: Pixel, X: Pixel> Into<[X; 2]> for LogicalSize<P> {
-    fn into(self: Self) -> [X; 2] {
-        [self.width.cast(), self.height.cast()]
+impl From<(ScaleFactor, ScaleFactor)> for ScaleFactor2D {
+    #[inline]
+    fn from((x, y): (ScaleFactor, ScaleFactor)) -> Self {
+        ScaleFactor2D::new(x, y)
     }
 }
 
-/// A size represented in physical pixels.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub struct PhysicalSize<P> {
-    pub width: P,
-    pub height: P,
+/// A fallible conversion into a [`ScaleFactor`], which is what the `try_` conversions of lengths
+/// accept.
+///
+/// It's implemented for the same types as `Into<ScaleFactor>`, but returns an error instead of
+/// panicking if an `f64` isn't a valid scale factor.
+///
+/// [`ScaleFactor`]: struct.ScaleFactor.html
+pub trait TryIntoScaleFactor {
+    fn try_into_scale_factor(self) -> Result<ScaleFactor, Error>;
 }
 
-impl<P> PhysicalSize<P> {
+impl TryIntoScaleFactor for f64 {
     #[inline]
-    pub const fn new(width: P, height: P) -> Self {
-        PhysicalSize { width, height }
+    fn try_into_scale_factor(self) -> Result<ScaleFactor, Error> {
+        ScaleFactor::new(self)
     }
 }
 
-impl<P: Pixel> PhysicalSize<P> {
+impl TryIntoScaleFactor for ScaleFactor {
     #[inline]
-    pub fn from_logical<T: Into<LogicalSize<X>>, X: Pixel>(logical: T, dpi_factor: f64) -> Self {
-        logical.into().to_physical(dpi_factor)
+    fn try_into_scale_factor(self) -> Result<ScaleFactor, Error> {
+        Ok(self)
     }
+}
 
+impl TryIntoScaleFactor for FractionalScale {
     #[inline]
-    pub fn to_logical<X: Pixel>(&self, dpi_factor: f64) -> LogicalSize<X> {
-        assert!(validate_scale_factor(dpi_factor));
-        let width = self.width.into() / dpi_factor;
-        let height = self.height.into() / dpi_factor;
-        LogicalSize::new(width, height).cast()
+    fn try_into_scale_factor(self) -> Result<ScaleFactor, Error> {
+        Ok(self.into())
     }
+}
+
+/// A fallible conversion into a [`ScaleFactor2D`], which is what the `try_` conversions of
+/// two-dimensional types accept.
+///
+/// It's implemented for the same types as `Into<ScaleFactor2D>`, but returns an error instead of
+/// panicking if an `f64` isn't a valid scale factor.
+///
+/// [`ScaleFactor2D`]: struct.ScaleFactor2D.html
+pub trait TryIntoScaleFactor2D {
+    fn try_into_scale_factor_2d(self) -> Result<ScaleFactor2D, Error>;
+}
 
+impl<T: TryIntoScaleFactor> TryIntoScaleFactor2D for T {
     #[inline]
-    pub fn cast<X: Pixel>(&self) -> PhysicalSize<X> {
-        PhysicalSize {
-            width: self.width.cast(),
-            height: self.height.cast(),
-        }
+    fn try_into_scale_factor_2d(self) -> Result<ScaleFactor2D, Error> {
+        self.try_into_scale_factor().map(ScaleFactor2D::uniform)
     }
 }
 
-impl<P: Pixel, X: Pixel> From<(X, X)> for PhysicalSize<P> {
-    fn from((x, y): (X, X)) -> PhysicalSize<P> {
-        PhysicalSize::new(x.cast(), y.cast())
+impl TryIntoScaleFactor2D for ScaleFactor2D {
+    #[inline]
+    fn try_into_scale_factor_2d(self) -> Result<ScaleFactor2D, Error> {
+        Ok(self)
     }
 }
 
-impl<P: Pixel, X: Pixel> Into<(X, X)> for PhysicalSize<P> {
-    fn into(self: Self) -> (X, X) {
-        (self.width.cast(), self.height.cast())
+impl TryIntoScaleFactor2D for (ScaleFactor, ScaleFactor) {
+    #[inline]
+    fn try_into_scale_factor_2d(self) -> Result<ScaleFactor2D, Error> {
+        Ok(self.into())
     }
 }
 
-impl<P: Pixel, X: Pixel> From<[X; 2]> for PhysicalSize<P> {
-    fn from([x, y]: [X; 2]) -> PhysicalSize<P> {
-        PhysicalSize::new(x.cast(), y.cast())
-    }
+/// A Wayland fractional scale, as sent by `wp_fractional_scale_v1`: a numerator over a fixed
+/// denominator of `120`.
+///
+/// Unlike [`ScaleFactor`], this is exact, which matters because the protocol specifies exactly how
+/// the buffer and viewport sizes derive from it. Surfaces using fractional scales keep a buffer
+/// scale of `1`, and use `wp_viewporter` to map their buffer onto the surface-local (logical)
+/// size, which is the viewport destination.
+///
+/// Compositors without the protocol only send integer scales, with `wl_output.scale` and
+/// `wl_surface.preferred_buffer_scale`. Those can be represented with [`from_integer`], and used
+/// with [`surface_size`] for surfaces relying on `wl_surface.set_buffer_scale` instead of a
+/// viewport.
+///
+/// [`ScaleFactor`]: struct.ScaleFactor.html
+/// [`from_integer`]: #method.from_integer
+/// [`surface_size`]: #method.surface_size
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(Serialize, Deserialize),
+    serde(try_from = "u32", into = "u32")
+)]
+pub struct FractionalScale(u32);
+
+/// Divides, rounding halfway values away from zero, and saturates at `u32::MAX`.
+#[inline]
+fn div_round(numerator: u64, denominator: u64) -> u32 {
+    ((numerator * 2 + denominator) / (denominator * 2)).min(u32::MAX as u64) as u32
 }
 
-impl<P: Pixel, X: Pixel> Into<[X; 2]> for PhysicalSize<P> {
-    fn into(self: Self) -> [X; 2] {
-        [self.width.cast(), self.height.cast()]
+impl FractionalScale {
+    /// The denominator all fractional scales are expressed over.
+    pub const DENOMINATOR: u32 = 120;
+
+    /// A scale of `1`.
+    pub const IDENTITY: FractionalScale = FractionalScale(FractionalScale::DENOMINATOR);
+
+    /// Creates a scale of `numerator / 120`, e.g. from a `preferred_scale` event. Returns an error
+    /// if `numerator` is `0`.
+    #[inline]
+    pub fn new(numerator: u32) -> Result<Self, Error> {
+        if numerator == 0 {
+            Err(lmake_error!(ErrorType::InvalidScaleFactor(0.0)))
+        } else {
+            Ok(FractionalScale(numerator))
+        }
     }
-}
 
-/// A size that's either physical or logical.
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub enum Size {
-    Physical(PhysicalSize<u32>),
-    Logical(LogicalSize<f64>),
-}
+    /// Creates a scale from an integer buffer scale, e.g. from `wl_output.scale`. Returns an error
+    /// if `scale` is `0` or too large to be represented.
+    #[inline]
+    pub fn from_integer(scale: u32) -> Result<Self, Error> {
+        match scale.checked_mul(FractionalScale::DENOMINATOR) {
+            Some(numerator) if numerator != 0 => Ok(FractionalScale(numerator)),
+            _ => Err(lmake_error!(ErrorType::InvalidScaleFactor(scale as f64))),
+        }
+    }
 
-impl Size {
-    pub fn new<S: Into<Size>>(size: S) -> Size {
-        size.into()
+    #[inline]
+    pub fn numerator(self) -> u32 {
+        self.0
     }
 
-    pub fn to_logical<P: Pixel>(&self, dpi_factor: f64) -> LogicalSize<P> {
-        match *self {
-            Size::Physical(size) => size.to_logical(dpi_factor),
-            Size::Logical(size) => size.cast(),
+    /// Returns the scale as an integer, if it is one.
+    #[inline]
+    pub fn integer(self) -> Option<u32> {
+        let scale = self.0 / FractionalScale::DENOMINATOR;
+        if scale * FractionalScale::DENOMINATOR == self.0 {
+            Some(scale)
+        } else {
+            None
         }
     }
 
-    pub fn to_physical<P: Pixel>(&self, dpi_factor: f64) -> PhysicalSize<P> {
-        match *self {
-            Size::Physical(size) => size.cast(),
-            Size::Logical(size) => size.to_physical(dpi_factor),
+    /// Returns the smallest integer scale at least as large as this one. Without `wp_viewporter`,
+    /// rendering at this buffer scale and letting the compositor downscale gives the sharpest
+    /// result.
+    #[inline]
+    pub fn ceil(self) -> u32 {
+        self.integer()
+            .unwrap_or(self.0 / FractionalScale::DENOMINATOR + 1)
+    }
+
+    #[inline]
+    pub fn to_scale_factor(self) -> ScaleFactor {
+        let divisor = gcd(self.0, FractionalScale::DENOMINATOR);
+        ScaleFactor {
+            factor: self.0 as f64 / FractionalScale::DENOMINATOR as f64,
+            ratio: Some((self.0 / divisor, FractionalScale::DENOMINATOR / divisor)),
         }
     }
-}
 
-impl<P: Pixel> From<PhysicalSize<P>> for Size {
+    /// Returns the size of the buffer to render for a surface of the given surface-local size,
+    /// rounding halfway values away from zero as the protocol requires.
+    ///
+    /// The viewport destination should be set to `surface_size`.
     #[inline]
-    fn from(size: PhysicalSize<P>) -> Size {
-        Size::Physical(size.cast())
+    pub fn buffer_size(self, surface_size: LogicalSize<u32>) -> PhysicalSize<u32> {
+        let scale = |value: u32| {
+            div_round(
+                value as u64 * self.0 as u64,
+                FractionalScale::DENOMINATOR as u64,
+            )
+        };
+        PhysicalSize::new(scale(surface_size.width), scale(surface_size.height))
     }
-}
 
-impl<P: Pixel> From<LogicalSize<P>> for Size {
+    /// Returns the viewport destination, i.e. the surface-local size, to use for a buffer of the
+    /// given size, rounding halfway values away from zero.
+    ///
+    /// For scales of at least `1`, this is the inverse of [`buffer_size`], so
+    /// `scale.viewport_destination(scale.buffer_size(size)) == size`.
+    ///
+    /// [`buffer_size`]: #method.buffer_size
     #[inline]
-    fn from(size: LogicalSize<P>) -> Size {
-        Size::Logical(size.cast())
+    pub fn viewport_destination(self, buffer_size: PhysicalSize<u32>) -> LogicalSize<u32> {
+        let unscale = |value: u32| {
+            div_round(
+                value as u64 * FractionalScale::DENOMINATOR as u64,
+                self.0 as u64,
+            )
+        };
+        LogicalSize::new(unscale(buffer_size.width), unscale(buffer_size.height))
     }
-}
 
-/// A position that's either physical or logical.
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
-pub enum Position {
-    Physical(PhysicalPosition<i32>),
-    Logical(LogicalPosition<f64>),
+    /// Returns the surface-local size of a buffer attached without a viewport, with its buffer
+    /// scale set to this scale through `wl_surface.set_buffer_scale`.
+    ///
+    /// Returns an error if this isn't an integer scale, or if the buffer's dimensions aren't
+    /// multiples of it, which compositors treat as a protocol error.
+    pub fn surface_size(self, buffer_size: PhysicalSize<u32>) -> Result<LogicalSize<u32>, Error> {
+        let scale = self.integer().ok_or_else(|| {
+            lmake_error!(ErrorType::BadApiUsage(format!(
+                "{}/120 is not an integer buffer scale",
+                self.0
+            )))
+        })?;
+        let surface_size = LogicalSize::new(buffer_size.width / scale, buffer_size.height / scale);
+        if surface_size.width * scale != buffer_size.width
+            || surface_size.height * scale != buffer_size.height
+        {
+            return Err(lmake_error!(ErrorType::BadApiUsage(format!(
+                "a {}x{} buffer is not a multiple of the buffer scale {}",
+                buffer_size.width, buffer_size.height, scale
+            ))));
+        }
+        Ok(surface_size)
+    }
 }
 
-impl Position {
-    pub fn new<S: Into<Position>>(position: S) -> Position {
-        position.into()
+impl Default for FractionalScale {
+    #[inline]
+    fn default() -> Self {
+        FractionalScale::IDENTITY
     }
+}
 
-    pub fn to_logical<P: Pixel>(&self, dpi_factor: f64) -> LogicalPosition<P> {
-        match *self {
-            Position::Physical(position) => position.to_logical(dpi_factor),
-            Position::Logical(position) => position.cast(),
-        }
+impl std::convert::TryFrom<u32> for FractionalScale {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(numerator: u32) -> Result<Self, Error> {
+        FractionalScale::new(numerator)
     }
+}
 
-    pub fn to_physical<P: Pixel>(&self, dpi_factor: f64) -> PhysicalPosition<P> {
-        match *self {
-            Position::Physical(position) => position.cast(),
-            Position::Logical(position) => position.to_physical(dpi_factor),
-        }
+impl From<FractionalScale> for u32 {
+    #[inline]
+    fn from(scale: FractionalScale) -> Self {
+        scale.0
     }
 }
 
-impl<P: Pixel> From<PhysicalPosition<P>> for Position {
+impl From<FractionalScale> for ScaleFactor {
     #[inline]
-    fn from(position: PhysicalPosition<P>) -> Position {
-        Position::Physical(position.cast())
+    fn from(scale: FractionalScale) -> Self {
+        scale.to_scale_factor()
     }
 }
 
-impl<P: Pixel> From<LogicalPosition<P>> for Position {
+/// How [`scale_factor_from_mm`] rounds the factors it computes.
+///
+/// [`scale_factor_from_mm`]: fn.scale_factor_from_mm.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum ScaleFactorSnapping {
+    /// Don't round the scale factor.
+    Exact,
+    /// Round to the nearest multiple of `1/12`. This is what winit does on X11.
+    Twelfths,
+    /// Round to the nearest multiple of `0.25`, like the factors offered by Windows' display
+    /// settings.
+    Quarters,
+    /// Round to the nearest integer, like Wayland compositors without fractional scaling.
+    Integer,
+}
+
+impl Default for ScaleFactorSnapping {
     #[inline]
-    fn from(position: LogicalPosition<P>) -> Position {
-        Position::Logical(position.cast())
+    fn default() -> Self {
+        ScaleFactorSnapping::Twelfths
     }
 }
 
-/// Rectangles to submit as buffer damage.
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Rect {
-    pub pos: PhysicalPosition<u32>,
-    pub size: PhysicalSize<u32>,
+/// Computes a scale factor from a monitor's resolution and its physical size in millimetres, so
+/// that a logical pixel is about as big as one on a 96 DPI monitor. This is how winit picks scale
+/// factors on X11 when `Xft.dpi` isn't set, using the sizes reported by XRandR.
+///
+/// Monitors commonly report bogus sizes, so a factor of `1.0` is returned when:
+///
+/// - either dimension is `0mm`, as reported by most projectors and some virtual machines;
+/// - the size is really an aspect ratio, as reported by some TVs and projectors (e.g. `160x90mm`
+///   or `16x10mm`);
+/// - the resulting factor would be larger than `20.0`.
+///
+/// The factor is never smaller than `1.0`.
+pub fn scale_factor_from_mm(
+    size: PhysicalSize<u32>,
+    size_mm: (u32, u32),
+    snapping: ScaleFactorSnapping,
+) -> ScaleFactor {
+    trusted_scale_factor_from_mm(size, size_mm, snapping).unwrap_or(ScaleFactor::IDENTITY)
+}
+
+/// Like [`scale_factor_from_mm`], but returns `None` instead of `1.0` for bogus sizes.
+///
+/// [`scale_factor_from_mm`]: fn.scale_factor_from_mm.html
+fn trusted_scale_factor_from_mm(
+    size: PhysicalSize<u32>,
+    (width_mm, height_mm): (u32, u32),
+    snapping: ScaleFactorSnapping,
+) -> Option<ScaleFactor> {
+    const ASPECT_RATIO_SIZES: [(u32, u32); 6] = [
+        (1600, 900),
+        (1600, 1000),
+        (160, 90),
+        (160, 100),
+        (16, 9),
+        (16, 10),
+    ];
+
+    if width_mm == 0 || height_mm == 0 || ASPECT_RATIO_SIZES.contains(&(width_mm, height_mm)) {
+        return None;
+    }
+
+    let pixels = size.width as f64 * size.height as f64;
+    let millimetres = width_mm as f64 * height_mm as f64;
+    let dpi_factor = (pixels / millimetres).sqrt() * 25.4 / 96.0;
+    let dpi_factor = match snapping {
+        ScaleFactorSnapping::Exact => dpi_factor,
+        ScaleFactorSnapping::Twelfths => (dpi_factor * 12.0).round() / 12.0,
+        ScaleFactorSnapping::Quarters => (dpi_factor * 4.0).round() / 4.0,
+        ScaleFactorSnapping::Integer => dpi_factor.round(),
+    };
+    if dpi_factor <= 20.0 {
+        ScaleFactor::new(dpi_factor.max(1.0)).ok()
+    } else {
+        None
+    }
+}
+
+/// The name of the environment variable used to override the scale factor on X11.
+pub const X11_SCALE_FACTOR_ENV_VAR: &str = "WINIT_X11_SCALE_FACTOR";
+
+/// The values [`resolve_x11_scale_factor`] picks an X11 monitor's scale factor from.
+///
+/// [`resolve_x11_scale_factor`]: fn.resolve_x11_scale_factor.html
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct X11ScaleFactorInputs<'a> {
+    /// The value of the `WINIT_X11_SCALE_FACTOR` environment variable, if set.
+    pub env_var: Option<&'a str>,
+    /// The value of `Xft.dpi` in the X resource database, if set.
+    pub xft_dpi: Option<f64>,
+    /// The monitor's resolution and size in millimetres as reported by XRandR, if supported.
+    pub randr: Option<(PhysicalSize<u32>, (u32, u32))>,
+    /// The screen's resolution and size in millimetres as reported by Xinerama/Xlib.
+    pub xinerama: Option<(PhysicalSize<u32>, (u32, u32))>,
+}
+
+/// Where the scale factor picked by [`resolve_x11_scale_factor`] came from.
+///
+/// [`resolve_x11_scale_factor`]: fn.resolve_x11_scale_factor.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum X11ScaleFactorSource {
+    EnvVar,
+    XftDpi,
+    RandR,
+    Xinerama,
+    /// None of the sources were available, so `1.0` was used.
+    Default,
+}
+
+/// Why [`resolve_x11_scale_factor`] skipped a source.
+///
+/// [`resolve_x11_scale_factor`]: fn.resolve_x11_scale_factor.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum X11ScaleFactorRejection {
+    /// The source wasn't available.
+    NotSet,
+    /// `WINIT_X11_SCALE_FACTOR` was set to `randr`, deferring to XRandR.
+    DeferredToRandR,
+    /// `WINIT_X11_SCALE_FACTOR` was set to `randr`, which skips `Xft.dpi`.
+    OverriddenByEnvVar,
+    /// The source gave this value, which isn't a valid scale factor or DPI.
+    Invalid(f64),
+    /// The millimetre size is bogus, as described in [`scale_factor_from_mm`].
+    ///
+    /// [`scale_factor_from_mm`]: fn.scale_factor_from_mm.html
+    BogusSize((u32, u32)),
+}
+
+/// The result of [`resolve_x11_scale_factor`].
+///
+/// [`resolve_x11_scale_factor`]: fn.resolve_x11_scale_factor.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct X11ScaleFactor {
+    pub scale_factor: ScaleFactor,
+    pub source: X11ScaleFactorSource,
+    /// The sources which take precedence over `source`, and why each was skipped.
+    pub rejected: Vec<(X11ScaleFactorSource, X11ScaleFactorRejection)>,
+}
+
+/// Picks the scale factor of an X11 monitor, following the precedence described in the
+/// [module docs](index.html#how-is-the-scale-factor-calculated):
+///
+/// 1. `WINIT_X11_SCALE_FACTOR`, if it's a number.
+/// 2. `Xft.dpi` divided by 96, unless `WINIT_X11_SCALE_FACTOR` is `randr`.
+/// 3. The monitor's XRandR resolution and millimetre size, using [`scale_factor_from_mm`].
+/// 4. The screen's Xinerama/Xlib resolution and millimetre size, likewise.
+/// 5. `1.0`.
+///
+/// Sources with a bogus millimetre size are skipped, rather than giving the `1.0`
+/// [`scale_factor_from_mm`] falls back to.
+///
+/// An empty `WINIT_X11_SCALE_FACTOR` is treated as unset. Any other value that isn't `randr` (in
+/// any case) or a valid scale factor is a configuration mistake the user should hear about, so
+/// an error is returned rather than falling back to the other sources.
+///
+/// [`scale_factor_from_mm`]: fn.scale_factor_from_mm.html
+pub fn resolve_x11_scale_factor(
+    inputs: &X11ScaleFactorInputs<'_>,
+) -> Result<X11ScaleFactor, Error> {
+    use self::{X11ScaleFactorRejection as Rejection, X11ScaleFactorSource as Source};
+
+    let mut rejected = Vec::new();
+    let env_var = inputs.env_var.filter(|var| !var.is_empty());
+    let use_randr = env_var
+        .map(|var| var.eq_ignore_ascii_case("randr"))
+        .unwrap_or(false);
+    match env_var {
+        Some(_) if use_randr => rejected.push((Source::EnvVar, Rejection::DeferredToRandR)),
+        Some(var) => {
+            let dpi_factor = var.parse::<f64>().map_err(|_| {
+                lmake_error!(ErrorType::InvalidEnvVar {
+                    name: X11_SCALE_FACTOR_ENV_VAR,
+                    value: var.to_owned(),
+                })
+            })?;
+            return Ok(X11ScaleFactor {
+                scale_factor: ScaleFactor::new(dpi_factor)?,
+                source: Source::EnvVar,
+                rejected,
+            });
+        }
+        None => rejected.push((Source::EnvVar, Rejection::NotSet)),
+    }
+
+    match inputs.xft_dpi {
+        _ if use_randr => rejected.push((Source::XftDpi, Rejection::OverriddenByEnvVar)),
+        Some(dpi) => match ScaleFactor::new(dpi / 96.0) {
+            Ok(scale_factor) => {
+                return Ok(X11ScaleFactor {
+                    scale_factor,
+                    source: Source::XftDpi,
+                    rejected,
+                })
+            }
+            Err(_) => rejected.push((Source::XftDpi, Rejection::Invalid(dpi))),
+        },
+        None => rejected.push((Source::XftDpi, Rejection::NotSet)),
+    }
+
+    for &(source, sizes) in &[
+        (Source::RandR, inputs.randr),
+        (Source::Xinerama, inputs.xinerama),
+    ] {
+        let (size, size_mm) = match sizes {
+            Some(sizes) => sizes,
+            None => {
+                rejected.push((source, Rejection::NotSet));
+                continue;
+            }
+        };
+        match trusted_scale_factor_from_mm(size, size_mm, ScaleFactorSnapping::Twelfths) {
+            Some(scale_factor) => {
+                return Ok(X11ScaleFactor {
+                    scale_factor,
+                    source,
+                    rejected,
+                })
+            }
+            None => rejected.push((source, Rejection::BogusSize(size_mm))),
+        }
+    }
+
+    Ok(X11ScaleFactor {
+        scale_factor: ScaleFactor::IDENTITY,
+        source: Source::Default,
+        rejected,
+    })
+}
+
+/// Lets serde validate deserialized scale factors, as `ScaleFactor` can't implement
+/// `TryFrom<f64>` alongside `From<f64>`. Exact factors which aren't whole numbers are stored as a
+/// ratio, everything else as a plain number.
+#[cfg(feature = "serde_feature")]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawScaleFactor {
+    Factor(f64),
+    Ratio { numerator: u32, denominator: u32 },
+}
+
+#[cfg(feature = "serde_feature")]
+impl std::convert::TryFrom<RawScaleFactor> for ScaleFactor {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(raw: RawScaleFactor) -> Result<Self, Error> {
+        match raw {
+            RawScaleFactor::Factor(dpi_factor) => ScaleFactor::new(dpi_factor),
+            RawScaleFactor::Ratio {
+                numerator,
+                denominator,
+            } => ScaleFactor::from_ratio(numerator, denominator),
+        }
+    }
+}
+
+#[cfg(feature = "serde_feature")]
+impl From<ScaleFactor> for RawScaleFactor {
+    #[inline]
+    fn from(dpi_factor: ScaleFactor) -> Self {
+        match dpi_factor.ratio {
+            Some((numerator, denominator)) if denominator != 1 => RawScaleFactor::Ratio {
+                numerator,
+                denominator,
+            },
+            _ => RawScaleFactor::Factor(dpi_factor.factor),
+        }
+    }
+}
+
+/// Moves a logical coordinate to the nearest physical pixel boundary.
+///
+/// Halfway values are rounded towards positive infinity rather than away from zero, so that
+/// snapping gives the same result wherever a layout is positioned.
+#[inline]
+fn snap_to_pixel(value: f64, dpi_factor: ScaleFactor) -> f64 {
+    dpi_factor.unscale((dpi_factor.scale(value) + 0.5).floor())
+}
+
+#[inline]
+fn min_pixel<P: Pixel>(a: P, b: P) -> P {
+    if b.into() < a.into() {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn max_pixel<P: Pixel>(a: P, b: P) -> P {
+    if b.into() > a.into() {
+        b
+    } else {
+        a
+    }
+}
+
+/// A position represented in logical pixels.
+///
+/// The position is stored as floats, so please be careful. Casting floats to integers truncates the
+/// fractional part, which can cause noticable issues. To help with that, an `Into<(i32, i32)>`
+/// implementation is provided which does the rounding for you.
+pub type LogicalPosition<P> = typed::Position<P, Logical>;
+
+impl<P: Pixel> LogicalPosition<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalPosition<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalPosition<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> PhysicalPosition<X> {
+        let dpi_factor = dpi_factor.into();
+        let x = dpi_factor.x.scale(self.x.into());
+        let y = dpi_factor.y.scale(self.y.into());
+        PhysicalPosition::new(x, y).cast()
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    #[inline]
+    pub fn to_physical_with<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalPosition<X> {
+        self.to_physical::<f64>(dpi_factor).cast_with(rounding)
+    }
+
+    /// Returns the position moved to the nearest physical pixel boundary, so that content drawn
+    /// there isn't blurred across two pixels.
+    #[inline]
+    pub fn snap_to_pixels(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalPosition<f64> {
+        let dpi_factor = dpi_factor.into();
+        LogicalPosition::new(
+            snap_to_pixel(self.x.into(), dpi_factor.x),
+            snap_to_pixel(self.y.into(), dpi_factor.y),
+        )
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalPosition<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A position represented in physical pixels.
+pub type PhysicalPosition<P> = typed::Position<P, Physical>;
+
+impl<P: Pixel> PhysicalPosition<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalPosition<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalPosition<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalPosition<X> {
+        let dpi_factor = dpi_factor.into();
+        let x = dpi_factor.x.unscale(self.x.into());
+        let y = dpi_factor.y.unscale(self.y.into());
+        LogicalPosition::new(x, y).cast()
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalPosition<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Converts the position from being relative to the `from` corner of a surface of the given
+    /// size to being relative to its `to` corner.
+    ///
+    /// Positions are treated as lying on the edges between pixels, so the top-left corner of a
+    /// surface `height` pixels high is at `y = height` in bottom-left coordinates. If `self` refers
+    /// to a whole pixel rather than a point, convert a one pixel [`PhysicalRect`] instead.
+    ///
+    /// Positions outside the surface can end up negative, so only signed and floating point
+    /// positions can be converted.
+    ///
+    /// [`PhysicalRect`]: type.PhysicalRect.html
+    #[inline]
+    pub fn change_origin(
+        &self,
+        from: Origin,
+        to: Origin,
+        surface: PhysicalSize<u32>,
+    ) -> PhysicalPosition<P>
+    where
+        P: ops::Neg<Output = P>,
+    {
+        if from == to {
+            return *self;
+        }
+        let height: f64 = surface.height.into();
+        PhysicalPosition::new(self.x.into(), height - self.y.into()).cast()
+    }
+}
+
+/// A displacement between two positions, represented in logical pixels.
+///
+/// Subtracting two `LogicalPosition`s gives a `LogicalDelta`, which can be added to a position
+/// to move it. Only signed and floating point positions can be subtracted, since the delta may be
+/// negative; cast unsigned positions first.
+pub type LogicalDelta<P> = typed::Delta<P, Logical>;
+
+impl<P: Pixel> LogicalDelta<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalDelta<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalDelta<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalDelta<X> {
+        let dpi_factor = dpi_factor.into();
+        let x = dpi_factor.x.scale(self.x.into());
+        let y = dpi_factor.y.scale(self.y.into());
+        PhysicalDelta::new(x, y).cast()
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalDelta<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A displacement between two positions, represented in physical pixels.
+///
+/// Subtracting two `PhysicalPosition`s gives a `PhysicalDelta`, which can be added to a position
+/// to move it. Only signed and floating point positions can be subtracted, since the delta may be
+/// negative; cast unsigned positions first.
+pub type PhysicalDelta<P> = typed::Delta<P, Physical>;
+
+impl<P: Pixel> PhysicalDelta<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalDelta<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalDelta<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalDelta<X> {
+        let dpi_factor = dpi_factor.into();
+        let x = dpi_factor.x.unscale(self.x.into());
+        let y = dpi_factor.y.unscale(self.y.into());
+        LogicalDelta::new(x, y).cast()
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalDelta<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A size represented in logical pixels.
+pub type LogicalSize<P> = typed::Size<P, Logical>;
+
+impl<P: Pixel> LogicalSize<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalSize<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalSize<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalSize<X> {
+        let dpi_factor = dpi_factor.into();
+        let width = dpi_factor.x.scale(self.width.into());
+        let height = dpi_factor.y.scale(self.height.into());
+        PhysicalSize::new(width, height).cast()
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    #[inline]
+    pub fn to_physical_with<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalSize<X> {
+        self.to_physical::<f64>(dpi_factor).cast_with(rounding)
+    }
+
+    /// Returns the size rounded to a whole number of physical pixels.
+    ///
+    /// When laying out boxes, snap them with [`LogicalRect::snap_to_pixels`] instead, which snaps
+    /// both edges and so keeps adjacent boxes adjacent.
+    ///
+    /// [`LogicalRect::snap_to_pixels`]: type.LogicalRect.html#method.snap_to_pixels
+    #[inline]
+    pub fn snap_to_pixels(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalSize<f64> {
+        let dpi_factor = dpi_factor.into();
+        LogicalSize::new(
+            snap_to_pixel(self.width.into(), dpi_factor.x),
+            snap_to_pixel(self.height.into(), dpi_factor.y),
+        )
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalSize<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A size represented in physical pixels.
+pub type PhysicalSize<P> = typed::Size<P, Physical>;
+
+impl<P: Pixel> PhysicalSize<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalSize<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalSize<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalSize<X> {
+        let dpi_factor = dpi_factor.into();
+        let width = dpi_factor.x.unscale(self.width.into());
+        let height = dpi_factor.y.unscale(self.height.into());
+        LogicalSize::new(width, height).cast()
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalSize<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A size that's either physical or logical.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Size {
+    Physical(PhysicalSize<u32>),
+    Logical(LogicalSize<f64>),
+}
+
+impl Size {
+    pub fn new<S: Into<Size>>(size: S) -> Size {
+        size.into()
+    }
+
+    pub fn to_logical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalSize<P> {
+        match *self {
+            Size::Physical(size) => size.to_logical(dpi_factor),
+            Size::Logical(size) => size.cast(),
+        }
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_logical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalSize<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    pub fn to_physical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalSize<P> {
+        match *self {
+            Size::Physical(size) => size.cast(),
+            Size::Logical(size) => size.to_physical(dpi_factor),
+        }
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalSize<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    pub fn to_physical_with<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalSize<P> {
+        match *self {
+            Size::Physical(size) => size.cast_with(rounding),
+            Size::Logical(size) => size.to_physical_with(dpi_factor, rounding),
+        }
+    }
+
+    /// Returns `true` if either component is zero (or negative).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            Size::Physical(size) => size.is_empty(),
+            Size::Logical(size) => size.is_empty(),
+        }
+    }
+
+    /// Returns the width divided by the height, or `None` if the size is empty.
+    #[inline]
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        match *self {
+            Size::Physical(size) => size.aspect_ratio(),
+            Size::Logical(size) => size.aspect_ratio(),
+        }
+    }
+
+    /// Returns the component-wise minimum of the two sizes, in the same kind of pixels as `self`.
+    pub fn min(&self, other: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match *self {
+            Size::Physical(size) => Size::Physical(size.min(other.to_physical(dpi_factor))),
+            Size::Logical(size) => Size::Logical(size.min(other.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Returns the component-wise maximum of the two sizes, in the same kind of pixels as `self`.
+    pub fn max(&self, other: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match *self {
+            Size::Physical(size) => Size::Physical(size.max(other.to_physical(dpi_factor))),
+            Size::Logical(size) => Size::Logical(size.max(other.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Clamps each component between those of `min` and `max`, in the same kind of pixels as
+    /// `self`. If `min` is larger than `max`, `max` wins.
+    pub fn clamp(&self, min: Size, max: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        let dpi_factor = dpi_factor.into();
+        self.max(min, dpi_factor).min(max, dpi_factor)
+    }
+
+    /// Like [`LogicalSize::scale_to_fit`], in the same kind of pixels as `self`.
+    ///
+    /// [`LogicalSize::scale_to_fit`]: typed/struct.Size.html#method.scale_to_fit
+    pub fn scale_to_fit(&self, bounds: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match *self {
+            Size::Physical(size) => {
+                Size::Physical(size.scale_to_fit(bounds.to_physical(dpi_factor)))
+            }
+            Size::Logical(size) => Size::Logical(size.scale_to_fit(bounds.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Like [`LogicalSize::scale_to_fill`], in the same kind of pixels as `self`.
+    ///
+    /// [`LogicalSize::scale_to_fill`]: typed/struct.Size.html#method.scale_to_fill
+    pub fn scale_to_fill(&self, bounds: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match *self {
+            Size::Physical(size) => {
+                Size::Physical(size.scale_to_fill(bounds.to_physical(dpi_factor)))
+            }
+            Size::Logical(size) => Size::Logical(size.scale_to_fill(bounds.to_logical(dpi_factor))),
+        }
+    }
+}
+
+impl<P: Pixel> From<PhysicalSize<P>> for Size {
+    #[inline]
+    fn from(size: PhysicalSize<P>) -> Size {
+        Size::Physical(size.cast())
+    }
+}
+
+impl<P: Pixel> From<LogicalSize<P>> for Size {
+    #[inline]
+    fn from(size: LogicalSize<P>) -> Size {
+        Size::Logical(size.cast())
+    }
+}
+
+/// A position that's either physical or logical.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Position {
+    Physical(PhysicalPosition<i32>),
+    Logical(LogicalPosition<f64>),
+}
+
+impl Position {
+    pub fn new<S: Into<Position>>(position: S) -> Position {
+        position.into()
+    }
+
+    pub fn to_logical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalPosition<P> {
+        match *self {
+            Position::Physical(position) => position.to_logical(dpi_factor),
+            Position::Logical(position) => position.cast(),
+        }
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_logical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalPosition<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    pub fn to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> PhysicalPosition<P> {
+        match *self {
+            Position::Physical(position) => position.cast(),
+            Position::Logical(position) => position.to_physical(dpi_factor),
+        }
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalPosition<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    pub fn to_physical_with<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalPosition<P> {
+        match *self {
+            Position::Physical(position) => position.cast_with(rounding),
+            Position::Logical(position) => position.to_physical_with(dpi_factor, rounding),
+        }
+    }
+
+    /// Converts the position from being relative to the `from` corner of `surface` to being
+    /// relative to its `to` corner. The position stays logical or physical, and `dpi_factor` is
+    /// only used if `surface` isn't in the same pixels.
+    ///
+    /// See [`PhysicalPosition::change_origin`] for how positions are treated.
+    ///
+    /// [`PhysicalPosition::change_origin`]: type.PhysicalPosition.html#method.change_origin
+    pub fn change_origin(
+        &self,
+        from: Origin,
+        to: Origin,
+        surface: Size,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Position {
+        match *self {
+            Position::Physical(position) => {
+                let surface = surface.to_physical(dpi_factor);
+                Position::Physical(position.change_origin(from, to, surface))
+            }
+            Position::Logical(_) if from == to => *self,
+            Position::Logical(position) => {
+                let height = surface.to_logical::<f64>(dpi_factor).height;
+                Position::Logical(LogicalPosition::new(position.x, height - position.y))
+            }
+        }
+    }
+}
+
+impl<P: Pixel> From<PhysicalPosition<P>> for Position {
+    #[inline]
+    fn from(position: PhysicalPosition<P>) -> Position {
+        Position::Physical(position.cast())
+    }
+}
+
+impl<P: Pixel> From<LogicalPosition<P>> for Position {
+    #[inline]
+    fn from(position: LogicalPosition<P>) -> Position {
+        Position::Logical(position.cast())
+    }
+}
+
+/// A one-dimensional length represented in logical pixels, such as a stroke width, a corner
+/// radius or a scroll distance.
+///
+/// Lengths have no direction, so conversions take a single [`ScaleFactor`]. Where the horizontal
+/// and vertical factors differ, pass the one for the axis the length is measured along.
+///
+/// [`ScaleFactor`]: struct.ScaleFactor.html
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct LogicalUnit<P>(pub P);
+
+impl<P> LogicalUnit<P> {
+    #[inline]
+    pub const fn new(value: P) -> Self {
+        LogicalUnit(value)
+    }
+}
+
+impl<P: Pixel> LogicalUnit<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalUnit<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalUnit<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor>) -> PhysicalUnit<X> {
+        PhysicalUnit(dpi_factor.into().scale(self.0.into())).cast()
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    #[inline]
+    pub fn to_physical_with<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor>,
+        rounding: Rounding,
+    ) -> PhysicalUnit<X> {
+        self.to_physical::<f64>(dpi_factor).cast_with(rounding)
+    }
+
+    /// Returns the length rounded to a whole number of physical pixels, e.g. so that a one point
+    /// border is drawn as exactly one or two pixels rather than blurred across three.
+    #[inline]
+    pub fn snap_to_pixels(&self, dpi_factor: impl Into<ScaleFactor>) -> LogicalUnit<f64> {
+        LogicalUnit(snap_to_pixel(self.0.into(), dpi_factor.into()))
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<PhysicalUnit<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> LogicalUnit<X> {
+        LogicalUnit(self.0.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`. [`Outward`] ceils.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: enum.Rounding.html#variant.Outward
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> LogicalUnit<X> {
+        LogicalUnit(self.0.cast_with(rounding.upper()))
+    }
+
+    /// Like [`cast`], but returns an error if the value isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<LogicalUnit<X>, Error> {
+        Ok(LogicalUnit(self.0.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps the value to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> LogicalUnit<X> {
+        LogicalUnit(self.0.saturating_cast())
+    }
+}
+
+impl<P: Pixel, X: Pixel> From<X> for LogicalUnit<P> {
+    #[inline]
+    fn from(value: X) -> LogicalUnit<P> {
+        LogicalUnit(value.cast())
+    }
+}
+
+/// A one-dimensional length represented in physical pixels.
+///
+/// See [`LogicalUnit`] for how lengths are converted.
+///
+/// [`LogicalUnit`]: struct.LogicalUnit.html
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct PhysicalUnit<P>(pub P);
+
+impl<P> PhysicalUnit<P> {
+    #[inline]
+    pub const fn new(value: P) -> Self {
+        PhysicalUnit(value)
+    }
+}
+
+impl<P: Pixel> PhysicalUnit<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalUnit<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalUnit<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor>) -> LogicalUnit<X> {
+        LogicalUnit(dpi_factor.into().unscale(self.0.into())).cast()
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<LogicalUnit<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> PhysicalUnit<X> {
+        PhysicalUnit(self.0.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`. [`Outward`] ceils.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: enum.Rounding.html#variant.Outward
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> PhysicalUnit<X> {
+        PhysicalUnit(self.0.cast_with(rounding.upper()))
+    }
+
+    /// Like [`cast`], but returns an error if the value isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<PhysicalUnit<X>, Error> {
+        Ok(PhysicalUnit(self.0.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps the value to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> PhysicalUnit<X> {
+        PhysicalUnit(self.0.saturating_cast())
+    }
+}
+
+impl<P: Pixel, X: Pixel> From<X> for PhysicalUnit<P> {
+    #[inline]
+    fn from(value: X) -> PhysicalUnit<P> {
+        PhysicalUnit(value.cast())
+    }
+}
+
+/// A length that's either physical or logical.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum PixelUnit {
+    Physical(PhysicalUnit<i32>),
+    Logical(LogicalUnit<f64>),
+}
+
+impl PixelUnit {
+    pub fn new<S: Into<PixelUnit>>(unit: S) -> PixelUnit {
+        unit.into()
+    }
+
+    pub fn to_logical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor>) -> LogicalUnit<P> {
+        match *self {
+            PixelUnit::Physical(unit) => unit.to_logical(dpi_factor),
+            PixelUnit::Logical(unit) => unit.cast(),
+        }
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_logical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<LogicalUnit<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    pub fn to_physical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor>) -> PhysicalUnit<P> {
+        match *self {
+            PixelUnit::Physical(unit) => unit.cast(),
+            PixelUnit::Logical(unit) => unit.to_physical(dpi_factor),
+        }
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor,
+    ) -> Result<PhysicalUnit<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    pub fn to_physical_with<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor>,
+        rounding: Rounding,
+    ) -> PhysicalUnit<P> {
+        match *self {
+            PixelUnit::Physical(unit) => unit.cast_with(rounding),
+            PixelUnit::Logical(unit) => unit.to_physical_with(dpi_factor, rounding),
+        }
+    }
+}
+
+impl<P: Pixel> From<PhysicalUnit<P>> for PixelUnit {
+    #[inline]
+    fn from(unit: PhysicalUnit<P>) -> PixelUnit {
+        PixelUnit::Physical(unit.cast())
+    }
+}
+
+impl<P: Pixel> From<LogicalUnit<P>> for PixelUnit {
+    #[inline]
+    fn from(unit: LogicalUnit<P>) -> PixelUnit {
+        PixelUnit::Logical(unit.cast())
+    }
+}
+
+/// The corner of a surface from which coordinates are measured.
+///
+/// Winit, like most windowing systems, puts the origin in the top-left corner with the y axis
+/// pointing down. OpenGL (e.g. `glScissor`, `glReadPixels`), EGL's buffer damage and macOS's
+/// `NSView` put it in the bottom-left corner, with the y axis pointing up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Origin {
+    TopLeft,
+    BottomLeft,
+}
+
+impl Default for Origin {
+    #[inline]
+    fn default() -> Self {
+        Origin::TopLeft
+    }
+}
+
+/// A rectangle represented in logical pixels.
+///
+/// The rectangle spans from `pos` (inclusive) to `pos + size` (exclusive), with the origin in the
+/// top-left corner.
+pub type LogicalRect<P> = typed::Rect<P, Logical>;
+
+impl<P: Pixel> LogicalRect<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalRect<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalRect<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalRect<X> {
+        let dpi_factor = dpi_factor.into();
+        PhysicalRect::new(
+            self.pos.to_physical(dpi_factor),
+            self.size.to_physical(dpi_factor),
+        )
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    #[inline]
+    pub fn to_physical_with<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalRect<X> {
+        self.to_physical::<f64>(dpi_factor).cast_with(rounding)
+    }
+
+    /// Converts the rectangle to physical pixels, flooring its top-left edges and ceiling its
+    /// bottom-right edges so that the result always covers the whole logical area.
+    #[inline]
+    pub fn to_physical_outward<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> PhysicalRect<X> {
+        self.to_physical_with(dpi_factor, Rounding::Outward)
+    }
+
+    /// Returns the rectangle with each edge moved to the nearest physical pixel boundary.
+    ///
+    /// Edges are snapped independently, so two rectangles sharing an edge still share it after
+    /// snapping, and their sizes may differ by a pixel even if they were equal before.
+    pub fn snap_to_pixels(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalRect<f64> {
+        let dpi_factor = dpi_factor.into();
+        let (left, top, right, bottom) = self.edges();
+        LogicalRect::from_edges(
+            snap_to_pixel(left, dpi_factor.x),
+            snap_to_pixel(top, dpi_factor.y),
+            snap_to_pixel(right, dpi_factor.x),
+            snap_to_pixel(bottom, dpi_factor.y),
+        )
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalRect<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// A rectangle represented in physical pixels.
+///
+/// The rectangle spans from `pos` (inclusive) to `pos + size` (exclusive), with the origin in the
+/// top-left corner.
+pub type PhysicalRect<P> = typed::Rect<P, Physical>;
+
+impl<P: Pixel> PhysicalRect<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalRect<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalRect<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalRect<X> {
+        let dpi_factor = dpi_factor.into();
+        LogicalRect::new(
+            self.pos.to_logical(dpi_factor),
+            self.size.to_logical(dpi_factor),
+        )
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalRect<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Converts the rectangle from being relative to the `from` corner of a surface of the given
+    /// size to being relative to its `to` corner.
+    ///
+    /// `pos` is always the corner of the rectangle closest to the origin, so a rectangle covering
+    /// the top row of pixels has `pos.y == 0` with a top-left origin, and
+    /// `pos.y == surface.height - 1` with a bottom-left one.
+    ///
+    /// Rectangles sticking out of the surface can end up with a negative position, so only signed
+    /// and floating point rectangles can be converted.
+    #[inline]
+    pub fn change_origin(
+        &self,
+        from: Origin,
+        to: Origin,
+        surface: PhysicalSize<u32>,
+    ) -> PhysicalRect<P>
+    where
+        P: ops::Neg<Output = P>,
+    {
+        if from == to {
+            return *self;
+        }
+        let (left, top, right, bottom) = self.edges();
+        let height: f64 = surface.height.into();
+        PhysicalRect::from_edges(left, height - bottom, right, height - top)
+    }
+}
+
+/// A rectangle that's either physical or logical.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Rect {
+    Physical(PhysicalRect<i32>),
+    Logical(LogicalRect<f64>),
+}
+
+impl Rect {
+    pub fn new<S: Into<Rect>>(rect: S) -> Rect {
+        rect.into()
+    }
+
+    pub fn to_logical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalRect<P> {
+        match *self {
+            Rect::Physical(rect) => rect.to_logical(dpi_factor),
+            Rect::Logical(rect) => rect.cast(),
+        }
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_logical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalRect<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    pub fn to_physical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalRect<P> {
+        match *self {
+            Rect::Physical(rect) => rect.cast(),
+            Rect::Logical(rect) => rect.to_physical(dpi_factor),
+        }
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalRect<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    pub fn to_physical_with<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalRect<P> {
+        match *self {
+            Rect::Physical(rect) => rect.cast_with(rounding),
+            Rect::Logical(rect) => rect.to_physical_with(dpi_factor, rounding),
+        }
+    }
+
+    /// Converts the rectangle from being relative to the `from` corner of `surface` to being
+    /// relative to its `to` corner. The rectangle stays logical or physical, and `dpi_factor` is
+    /// only used if `surface` isn't in the same pixels.
+    ///
+    /// See [`PhysicalRect::change_origin`] for how `pos` is treated.
+    ///
+    /// [`PhysicalRect::change_origin`]: type.PhysicalRect.html#method.change_origin
+    pub fn change_origin(
+        &self,
+        from: Origin,
+        to: Origin,
+        surface: Size,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Rect {
+        match *self {
+            Rect::Physical(rect) => {
+                let surface = surface.to_physical(dpi_factor);
+                Rect::Physical(rect.change_origin(from, to, surface))
+            }
+            Rect::Logical(_) if from == to => *self,
+            Rect::Logical(rect) => {
+                let height = surface.to_logical::<f64>(dpi_factor).height;
+                let (left, top, right, bottom) = rect.edges();
+                Rect::Logical(LogicalRect::from_edges(
+                    left,
+                    height - bottom,
+                    right,
+                    height - top,
+                ))
+            }
+        }
+    }
+}
+
+impl<P: Pixel> From<PhysicalRect<P>> for Rect {
+    #[inline]
+    fn from(rect: PhysicalRect<P>) -> Rect {
+        Rect::Physical(rect.cast())
+    }
+}
+
+impl<P: Pixel> From<LogicalRect<P>> for Rect {
+    #[inline]
+    fn from(rect: LogicalRect<P>) -> Rect {
+        Rect::Logical(rect.cast())
+    }
+}
+
+/// Insets represented in logical pixels.
+///
+/// Safe area insets, e.g. for notches and rounded screen corners, are generally reported in
+/// logical pixels, while client-side decoration margins and frame extents are physical.
+pub type LogicalInsets<P> = typed::Insets<P, Logical>;
+
+impl<P: Pixel> LogicalInsets<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalInsets<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    /// Like [`from_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_physical`]: #method.from_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_physical<T: Into<PhysicalInsets<X>>, X: Pixel>(
+        physical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        physical.into().try_to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalInsets<X> {
+        let dpi_factor = dpi_factor.into();
+        let (x, y) = (dpi_factor.x, dpi_factor.y);
+        PhysicalInsets::new(
+            x.scale(self.left.into()),
+            y.scale(self.top.into()),
+            x.scale(self.right.into()),
+            y.scale(self.bottom.into()),
+        )
+        .cast()
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    #[inline]
+    pub fn to_physical_with<X: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalInsets<X> {
+        self.to_physical::<f64>(dpi_factor).cast_with(rounding)
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_physical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalInsets<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// Insets represented in physical pixels.
+pub type PhysicalInsets<P> = typed::Insets<P, Physical>;
+
+impl<P: Pixel> PhysicalInsets<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalInsets<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl Into<ScaleFactor2D>,
+    ) -> Self {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    /// Like [`from_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`from_logical`]: #method.from_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_from_logical<T: Into<LogicalInsets<X>>, X: Pixel>(
+        logical: T,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<Self, Error> {
+        logical.into().try_to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalInsets<X> {
+        let dpi_factor = dpi_factor.into();
+        let (x, y) = (dpi_factor.x, dpi_factor.y);
+        LogicalInsets::new(
+            x.unscale(self.left.into()),
+            y.unscale(self.top.into()),
+            x.unscale(self.right.into()),
+            y.unscale(self.bottom.into()),
+        )
+        .cast()
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    #[inline]
+    pub fn try_to_logical<X: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalInsets<X>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+}
+
+/// Insets that are either physical or logical.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Insets {
+    Physical(PhysicalInsets<u32>),
+    Logical(LogicalInsets<f64>),
+}
+
+impl Insets {
+    pub fn new<S: Into<Insets>>(insets: S) -> Insets {
+        insets.into()
+    }
+
+    pub fn to_logical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> LogicalInsets<P> {
+        match *self {
+            Insets::Physical(insets) => insets.to_logical(dpi_factor),
+            Insets::Logical(insets) => insets.cast(),
+        }
+    }
+
+    /// Like [`to_logical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_logical`]: #method.to_logical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_logical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<LogicalInsets<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_logical::<f64>(dpi_factor).try_cast())
+    }
+
+    pub fn to_physical<P: Pixel>(&self, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalInsets<P> {
+        match *self {
+            Insets::Physical(insets) => insets.cast(),
+            Insets::Logical(insets) => insets.to_physical(dpi_factor),
+        }
+    }
+
+    /// Like [`to_physical`], but returns an error instead of panicking on an invalid `f64` scale
+    /// factor or saturating a result that doesn't fit. See [fallible conversions].
+    ///
+    /// [`to_physical`]: #method.to_physical
+    /// [fallible conversions]: index.html#fallible-conversions
+    pub fn try_to_physical<P: Pixel>(
+        &self,
+        dpi_factor: impl TryIntoScaleFactor2D,
+    ) -> Result<PhysicalInsets<P>, Error> {
+        dpi_factor
+            .try_into_scale_factor_2d()
+            .and_then(|dpi_factor| self.to_physical::<f64>(dpi_factor).try_cast())
+    }
+
+    /// Like [`to_physical`], but rounds using `rounding`.
+    ///
+    /// [`to_physical`]: #method.to_physical
+    pub fn to_physical_with<P: Pixel>(
+        &self,
+        dpi_factor: impl Into<ScaleFactor2D>,
+        rounding: Rounding,
+    ) -> PhysicalInsets<P> {
+        match *self {
+            Insets::Physical(insets) => insets.cast_with(rounding),
+            Insets::Logical(insets) => insets.to_physical_with(dpi_factor, rounding),
+        }
+    }
+
+    /// Takes the insets off `size`, in the same kind of pixels as `size`.
+    pub fn inset_size(&self, size: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match size {
+            Size::Physical(size) => Size::Physical(size.inset(self.to_physical(dpi_factor))),
+            Size::Logical(size) => Size::Logical(size.inset(self.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Adds the insets to `size`, in the same kind of pixels as `size`.
+    pub fn outset_size(&self, size: Size, dpi_factor: impl Into<ScaleFactor2D>) -> Size {
+        match size {
+            Size::Physical(size) => Size::Physical(size.outset(self.to_physical(dpi_factor))),
+            Size::Logical(size) => Size::Logical(size.outset(self.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Moves the edges of `rect` inwards by the insets, in the same kind of pixels as `rect`.
+    pub fn inset_rect(&self, rect: Rect, dpi_factor: impl Into<ScaleFactor2D>) -> Rect {
+        match rect {
+            Rect::Physical(rect) => Rect::Physical(rect.inset(self.to_physical(dpi_factor))),
+            Rect::Logical(rect) => Rect::Logical(rect.inset(self.to_logical(dpi_factor))),
+        }
+    }
+
+    /// Moves the edges of `rect` outwards by the insets, in the same kind of pixels as `rect`.
+    pub fn outset_rect(&self, rect: Rect, dpi_factor: impl Into<ScaleFactor2D>) -> Rect {
+        match rect {
+            Rect::Physical(rect) => Rect::Physical(rect.outset(self.to_physical(dpi_factor))),
+            Rect::Logical(rect) => Rect::Logical(rect.outset(self.to_logical(dpi_factor))),
+        }
+    }
+}
+
+impl<P: Pixel> From<PhysicalInsets<P>> for Insets {
+    #[inline]
+    fn from(insets: PhysicalInsets<P>) -> Insets {
+        Insets::Physical(insets.cast())
+    }
+}
+
+impl<P: Pixel> From<LogicalInsets<P>> for Insets {
+    #[inline]
+    fn from(insets: LogicalInsets<P>) -> Insets {
+        Insets::Logical(insets.cast())
+    }
+}
+
+/// An affine transform of physical coordinates, mapping `(x, y)` to
+/// `(xx * x + xy * y + x0, yx * x + yy * y + y0)`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct AffineTransform {
+    pub xx: f64,
+    pub yx: f64,
+    pub xy: f64,
+    pub yy: f64,
+    pub x0: f64,
+    pub y0: f64,
+}
+
+impl AffineTransform {
+    pub const IDENTITY: AffineTransform = AffineTransform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    #[inline]
+    pub const fn new(xx: f64, yx: f64, xy: f64, yy: f64, x0: f64, y0: f64) -> Self {
+        AffineTransform {
+            xx,
+            yx,
+            xy,
+            yy,
+            x0,
+            y0,
+        }
+    }
+
+    #[inline]
+    pub fn translation(dx: f64, dy: f64) -> Self {
+        AffineTransform::new(1.0, 0.0, 0.0, 1.0, dx, dy)
+    }
+
+    #[inline]
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        AffineTransform::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// A rotation around the origin by `radians`, which is clockwise on screen since the y axis
+    /// points down.
+    #[inline]
+    pub fn rotation(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        AffineTransform::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Returns the transform applying this one, then `next`.
+    pub fn then(&self, next: &AffineTransform) -> AffineTransform {
+        AffineTransform::new(
+            next.xx * self.xx + next.xy * self.yx,
+            next.yx * self.xx + next.yy * self.yx,
+            next.xx * self.xy + next.xy * self.yy,
+            next.yx * self.xy + next.yy * self.yy,
+            next.xx * self.x0 + next.xy * self.y0 + next.x0,
+            next.yx * self.x0 + next.yy * self.y0 + next.y0,
+        )
+    }
+
+    /// Returns the transform undoing this one, or `None` if it collapses the plane onto a line or
+    /// a point.
+    pub fn inverse(&self) -> Option<AffineTransform> {
+        let det = self.xx * self.yy - self.xy * self.yx;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let (xx, yx, xy, yy) = (self.yy / det, -self.yx / det, -self.xy / det, self.xx / det);
+        Some(AffineTransform::new(
+            xx,
+            yx,
+            xy,
+            yy,
+            -(xx * self.x0 + xy * self.y0),
+            -(yx * self.x0 + yy * self.y0),
+        ))
+    }
+
+    #[inline]
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.xx * x + self.xy * y + self.x0,
+            self.yx * x + self.yy * y + self.y0,
+        )
+    }
+}
+
+impl Default for AffineTransform {
+    #[inline]
+    fn default() -> Self {
+        AffineTransform::IDENTITY
+    }
+}
+
+/// A transform between the physical coordinates of a surface and those of its rotated or
+/// flipped counterpart, such as a buffer displayed with `wl_output.transform`, a rotated Android
+/// display or a rotated RandR output.
+///
+/// Transforms map coordinates from surface space, the untransformed surface, to buffer space, its
+/// transformed counterpart. In that direction rotations turn the surface clockwise, so a point on
+/// its right edge ends up on the bottom edge after [`Rotate90`], and flipped transforms mirror the
+/// result of the rotation horizontally. The [`inverse`] maps buffer space back to surface space,
+/// turning it counter-clockwise, which is the direction `wl_output.transform` names its values
+/// after. The first eight variants are in the order of those values.
+///
+/// The transformed surface keeps its top-left corner at the origin, so the results of rotations
+/// and flips only depend on the size of the surface, and are exact. [`Affine`] transforms are
+/// arbitrary, and rectangles are mapped to the smallest whole pixel rectangle covering them.
+///
+/// Transforms only apply to physical pixels, which is what buffers and outputs are measured in.
+/// Logical values, including those held by the [`Position`] and [`Rect`] enums, have to be
+/// converted with their `to_physical` functions first.
+///
+/// [`Rotate90`]: #variant.Rotate90
+/// [`inverse`]: #method.inverse
+/// [`Affine`]: #variant.Affine
+/// [`Position`]: enum.Position.html
+/// [`Rect`]: enum.Rect.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub enum Transform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+    Affine(AffineTransform),
+}
+
+const DIHEDRAL_TRANSFORMS: [Transform; 8] = [
+    Transform::Normal,
+    Transform::Rotate90,
+    Transform::Rotate180,
+    Transform::Rotate270,
+    Transform::Flipped,
+    Transform::Flipped90,
+    Transform::Flipped180,
+    Transform::Flipped270,
+];
+
+impl Transform {
+    /// The `[xx, yx, xy, yy]` coefficients of a rotation or flip, or the affine transform itself.
+    fn dihedral_matrix(self) -> Result<[i8; 4], AffineTransform> {
+        match self {
+            Transform::Normal => Ok([1, 0, 0, 1]),
+            Transform::Rotate90 => Ok([0, 1, -1, 0]),
+            Transform::Rotate180 => Ok([-1, 0, 0, -1]),
+            Transform::Rotate270 => Ok([0, -1, 1, 0]),
+            Transform::Flipped => Ok([-1, 0, 0, 1]),
+            Transform::Flipped90 => Ok([0, 1, 1, 0]),
+            Transform::Flipped180 => Ok([1, 0, 0, -1]),
+            Transform::Flipped270 => Ok([0, -1, -1, 0]),
+            Transform::Affine(affine) => Err(affine),
+        }
+    }
+
+    /// Returns the rotation or flip with the given `[xx, yx, xy, yy]` coefficients, if any.
+    fn from_dihedral_matrix(matrix: [i8; 4]) -> Option<Transform> {
+        DIHEDRAL_TRANSFORMS
+            .iter()
+            .copied()
+            .find(|transform| transform.dihedral_matrix() == Ok(matrix))
+    }
+
+    /// Returns the rotation or flip `affine` amounts to on a surface of the given size, if any, so
+    /// that compositions cancelling each other out compare equal to the plain variants.
+    fn collapse_affine(affine: AffineTransform, surface: PhysicalSize<u32>) -> Transform {
+        DIHEDRAL_TRANSFORMS
+            .iter()
+            .copied()
+            .find(|transform| affine_approx_eq(&transform.to_affine(surface), &affine))
+            .unwrap_or(Transform::Affine(affine))
+    }
+
+    /// Returns `true` if the transform swaps the width and height of the surface.
+    #[inline]
+    pub fn swaps_axes(self) -> bool {
+        match self.dihedral_matrix() {
+            Ok([xx, ..]) => xx == 0,
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the transform as an affine transform of the coordinates of a surface of the given
+    /// size.
+    pub fn to_affine(self, surface: PhysicalSize<u32>) -> AffineTransform {
+        let [xx, yx, xy, yy] = match self.dihedral_matrix() {
+            Ok(matrix) => matrix,
+            Err(affine) => return affine,
+        };
+        // Mirrored axes are moved back into the surface.
+        let (width, height) = (surface.width as f64, surface.height as f64);
+        let offset = |from_x: i8, from_y: i8| {
+            let mut offset = 0.0;
+            if from_x < 0 {
+                offset += width;
+            }
+            if from_y < 0 {
+                offset += height;
+            }
+            offset
+        };
+        AffineTransform::new(
+            xx.into(),
+            yx.into(),
+            xy.into(),
+            yy.into(),
+            offset(xx, xy),
+            offset(yx, yy),
+        )
+    }
+
+    /// Returns the transform applying this one to a surface of the given size, then `next` to
+    /// the result.
+    ///
+    /// If the result is a rotation or flip of the surface, it's returned as such rather than as an
+    /// [`Affine`] transform, e.g. when composing an affine transform with its inverse.
+    ///
+    /// [`Affine`]: #variant.Affine
+    pub fn then(self, next: Transform, surface: PhysicalSize<u32>) -> Transform {
+        if let (Ok([xx, yx, xy, yy]), Ok([n_xx, n_yx, n_xy, n_yy])) =
+            (self.dihedral_matrix(), next.dihedral_matrix())
+        {
+            let product = Transform::from_dihedral_matrix([
+                n_xx * xx + n_xy * yx,
+                n_yx * xx + n_yy * yx,
+                n_xx * xy + n_xy * yy,
+                n_yx * xy + n_yy * yy,
+            ]);
+            if let Some(product) = product {
+                return product;
+            }
+        }
+        let next = next.to_affine(self.transform_size(surface));
+        Transform::collapse_affine(self.to_affine(surface).then(&next), surface)
+    }
+
+    /// Returns the transform undoing this one, or `None` if it's an affine transform which can't
+    /// be undone.
+    ///
+    /// The inverse of an affine transform is only turned back into a rotation or flip if it's
+    /// [`Normal`], as the others depend on the size of the surface.
+    ///
+    /// [`Normal`]: #variant.Normal
+    pub fn inverse(self) -> Option<Transform> {
+        match self.dihedral_matrix() {
+            // Rotations and flips are orthogonal, so their inverse is their transpose.
+            Ok([xx, yx, xy, yy]) => Transform::from_dihedral_matrix([xx, xy, yx, yy]),
+            Err(affine) => affine.inverse().map(|inverse| {
+                if affine_approx_eq(&inverse, &AffineTransform::IDENTITY) {
+                    Transform::Normal
+                } else {
+                    Transform::Affine(inverse)
+                }
+            }),
+        }
+    }
+
+    /// Maps a position on a surface of the given size. Like with [`change_origin`], positions lie
+    /// on the edges between pixels; map a one pixel rectangle to map a whole pixel.
+    ///
+    /// [`change_origin`]: type.PhysicalPosition.html#method.change_origin
+    pub fn transform_position<P: Pixel>(
+        self,
+        position: PhysicalPosition<P>,
+        surface: PhysicalSize<u32>,
+    ) -> PhysicalPosition<P> {
+        let (x, y) = self
+            .to_affine(surface)
+            .transform_point(position.x.into(), position.y.into());
+        PhysicalPosition::new(x, y).cast()
+    }
+
+    /// Returns the size of a surface of the given size once transformed.
+    pub fn transform_size<P: Pixel>(self, size: PhysicalSize<P>) -> PhysicalSize<P> {
+        match self {
+            Transform::Affine(affine) => {
+                let rect = PhysicalRect::new(PhysicalPosition::new(0.0, 0.0), size.cast());
+                transform_rect_bounds(&affine, rect)
+                    .size
+                    .cast_with(Rounding::Outward)
+            }
+            _ if self.swaps_axes() => PhysicalSize::new(size.height, size.width),
+            _ => size,
+        }
+    }
+
+    /// Maps a rectangle on a surface of the given size, e.g. to turn surface damage into buffer
+    /// damage.
+    pub fn transform_rect<P: Pixel>(
+        self,
+        rect: PhysicalRect<P>,
+        surface: PhysicalSize<u32>,
+    ) -> PhysicalRect<P> {
+        transform_rect_bounds(&self.to_affine(surface), rect.cast()).cast_with(Rounding::Outward)
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Transform::Normal
+    }
+}
+
+impl From<AffineTransform> for Transform {
+    #[inline]
+    fn from(affine: AffineTransform) -> Self {
+        Transform::Affine(affine)
+    }
+}
+
+/// Returns `true` if the two transforms are the same, give or take floating point noise.
+fn affine_approx_eq(a: &AffineTransform, b: &AffineTransform) -> bool {
+    let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0);
+    close(a.xx, b.xx)
+        && close(a.yx, b.yx)
+        && close(a.xy, b.xy)
+        && close(a.yy, b.yy)
+        && close(a.x0, b.x0)
+        && close(a.y0, b.y0)
+}
+
+/// Returns the bounding box of `rect` once transformed.
+fn transform_rect_bounds(affine: &AffineTransform, rect: PhysicalRect<f64>) -> PhysicalRect<f64> {
+    let (left, top, right, bottom) = rect.edges();
+    let corners = [
+        affine.transform_point(left, top),
+        affine.transform_point(right, top),
+        affine.transform_point(left, bottom),
+        affine.transform_point(right, bottom),
+    ];
+    let (mut min_x, mut min_y) = corners[0];
+    let (mut max_x, mut max_y) = corners[0];
+    for &(x, y) in &corners[1..] {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    PhysicalRect::from_edges(min_x, min_y, max_x, max_y)
+}
+
+/// Limits on the size of a window, as described by ICCCM's `WM_NORMAL_HINTS`.
+///
+/// Each limit can be given in either logical or physical pixels; they're all converted to whole
+/// physical pixels when the constraints are applied.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct SizeConstraints {
+    /// The smallest allowed size. Defaults to `base_size` if unset.
+    pub min_size: Option<Size>,
+    /// The largest allowed size.
+    pub max_size: Option<Size>,
+    /// The size that resize increments are counted from. Defaults to `min_size` if unset.
+    pub base_size: Option<Size>,
+    /// The window can only be `base_size` plus a whole number of increments.
+    pub resize_increments: Option<Size>,
+    /// The smallest allowed width to height ratio. Ignored unless it's finite and positive.
+    pub min_aspect_ratio: Option<f64>,
+    /// The largest allowed width to height ratio. Ignored unless it's finite and positive.
+    pub max_aspect_ratio: Option<f64>,
+}
+
+/// Rounds `value` up to a multiple of `increment`, ignoring floating point noise.
+#[inline]
+fn ceil_to_increment(value: f64, increment: i64) -> i64 {
+    let increment = increment as f64;
+    ((value / increment - 1e-9).ceil() * increment) as i64
+}
+
+impl SizeConstraints {
+    /// Returns the admissible size nearest to `size`, following the same rules as X11 window
+    /// managers applying `WM_NORMAL_HINTS`:
+    ///
+    /// 1. The size is clamped between the minimum and maximum sizes.
+    /// 2. It's shrunk to the base size plus a whole number of resize increments, or grown if that
+    ///    would take it below the minimum size.
+    /// 3. If its aspect ratio is out of range, the dimension that's too long is shrunk by whole
+    ///    increments, or if that would break the minimum size, the other one is grown, provided
+    ///    that doesn't break the maximum size. If a base size is set, it's subtracted from both
+    ///    dimensions before checking the ratio.
+    ///
+    /// If the minimum size is larger than the maximum size, the maximum size wins.
+    pub fn constrain(&self, size: Size, dpi_factor: impl Into<ScaleFactor2D>) -> PhysicalSize<u32> {
+        let dpi_factor = dpi_factor.into();
+        let physical = |size: Option<Size>| {
+            size.map(|size| {
+                let size = size.to_physical::<u32>(dpi_factor);
+                (size.width as i64, size.height as i64)
+            })
+        };
+        let explicit_base = physical(self.base_size);
+        let min = physical(self.min_size).or(explicit_base).unwrap_or((0, 0));
+        let base = explicit_base.unwrap_or(min);
+        let max = physical(self.max_size).unwrap_or((u32::MAX as i64, u32::MAX as i64));
+        let increment = physical(self.resize_increments)
+            .map(|(width, height)| (width.max(1), height.max(1)))
+            .unwrap_or((1, 1));
+
+        let size = size.to_physical::<u32>(dpi_factor);
+        let constrain_axis = |value: i64, min: i64, max: i64, base: i64, increment: i64| {
+            let value = value.max(min).min(max);
+            let mut snapped = base + (value - base).div_euclid(increment) * increment;
+            if snapped < min && snapped + increment <= max {
+                snapped += increment;
+            }
+            snapped
+        };
+        let mut width = constrain_axis(size.width as i64, min.0, max.0, base.0, increment.0);
+        let mut height = constrain_axis(size.height as i64, min.1, max.1, base.1, increment.1);
+
+        let (base_width, base_height) = explicit_base.unwrap_or((0, 0));
+        let valid_ratio = |ratio: &f64| ratio.is_finite() && *ratio > 0.0;
+        if let Some(min_aspect) = self.min_aspect_ratio.filter(valid_ratio) {
+            let (aspect_width, aspect_height) =
+                ((width - base_width) as f64, (height - base_height) as f64);
+            if aspect_width < aspect_height * min_aspect {
+                let delta =
+                    ceil_to_increment(aspect_height - aspect_width / min_aspect, increment.1);
+                if delta <= height - min.1 {
+                    height -= delta;
+                } else {
+                    let delta =
+                        ceil_to_increment(aspect_height * min_aspect - aspect_width, increment.0);
+                    if delta <= max.0 - width {
+                        width += delta;
+                    }
+                }
+            }
+        }
+        if let Some(max_aspect) = self.max_aspect_ratio.filter(valid_ratio) {
+            let (aspect_width, aspect_height) =
+                ((width - base_width) as f64, (height - base_height) as f64);
+            if aspect_width > aspect_height * max_aspect {
+                let delta =
+                    ceil_to_increment(aspect_width - aspect_height * max_aspect, increment.0);
+                if delta <= width - min.0 {
+                    width -= delta;
+                } else {
+                    let delta =
+                        ceil_to_increment(aspect_width / max_aspect - aspect_height, increment.1);
+                    if delta <= max.1 - height {
+                        height += delta;
+                    }
+                }
+            }
+        }
+
+        PhysicalSize::new(width.max(0) as u32, height.max(0) as u32)
+    }
+}
+
+/// The size a window should take after its scale factor changes, as returned by
+/// [`size_for_scale_factor_change`].
+///
+/// [`size_for_scale_factor_change`]: fn.size_for_scale_factor_change.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct ScaleFactorChangeSize {
+    /// The suggested new inner size.
+    pub physical: PhysicalSize<u32>,
+    /// The logical size the window had before the change, which `physical` tries to preserve.
+    ///
+    /// Because of rounding and size constraints, `physical` might not be exactly this size at the
+    /// new scale factor.
+    pub logical: LogicalSize<f64>,
+}
+
+/// Computes the inner size a window should take when its scale factor changes from
+/// `old_dpi_factor` to `new_dpi_factor`, e.g. because it was moved to another monitor.
+///
+/// The window keeps its logical size, rounded to the nearest physical pixel at the new scale
+/// factor, then `constraints` are applied if given. Backends should use this rather than doing the
+/// math themselves, so that all of them agree on the result.
+pub fn size_for_scale_factor_change(
+    current: PhysicalSize<u32>,
+    old_dpi_factor: impl Into<ScaleFactor2D>,
+    new_dpi_factor: impl Into<ScaleFactor2D>,
+    constraints: Option<&SizeConstraints>,
+) -> ScaleFactorChangeSize {
+    let new_dpi_factor = new_dpi_factor.into();
+    let logical = current.to_logical::<f64>(old_dpi_factor);
+    let physical = match constraints {
+        Some(constraints) => constraints.constrain(logical.into(), new_dpi_factor),
+        None => logical.to_physical(new_dpi_factor),
+    };
+    ScaleFactorChangeSize { physical, logical }
+}
+
+// Conversion traits, so that generic code can accept any of the types above.
+
+/// Conversion into physical pixels, implemented by every geometry type in this module.
+///
+/// Physical types are just cast to `X`, and lengths are scaled by the horizontal factor. Implement
+/// this for your own types to rescale whole structures of geometry with a single generic function.
+pub trait ToPhysical<X: Pixel> {
+    type Output;
+
+    fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output;
+}
+
+/// Conversion into logical pixels, implemented by every geometry type in this module.
+///
+/// Logical types are just cast to `X`, and lengths are scaled by the horizontal factor. Implement
+/// this for your own types to rescale whole structures of geometry with a single generic function.
+pub trait ToLogical<X: Pixel> {
+    type Output;
+
+    fn to_logical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output;
+}
+
+/// Construction of a logical value from a physical one.
+pub trait FromPhysical<T>: Sized {
+    fn from_physical(physical: T, dpi_factor: impl Into<ScaleFactor2D>) -> Self;
+}
+
+/// Construction of a physical value from a logical one.
+pub trait FromLogical<T>: Sized {
+    fn from_logical(logical: T, dpi_factor: impl Into<ScaleFactor2D>) -> Self;
+}
+
+macro_rules! impl_conversion_traits {
+    ($logical:ident, $physical:ident $(, $axis:ident)?) => {
+        impl<P: Pixel, X: Pixel> ToPhysical<X> for $logical<P> {
+            type Output = $physical<X>;
+
+            #[inline]
+            fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> $physical<X> {
+                $logical::to_physical(self, Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+
+        impl<P: Pixel, X: Pixel> ToLogical<X> for $logical<P> {
+            type Output = $logical<X>;
+
+            #[inline]
+            fn to_logical(&self, _dpi_factor: impl Into<ScaleFactor2D>) -> $logical<X> {
+                self.cast()
+            }
+        }
+
+        impl<P: Pixel, X: Pixel> ToPhysical<X> for $physical<P> {
+            type Output = $physical<X>;
+
+            #[inline]
+            fn to_physical(&self, _dpi_factor: impl Into<ScaleFactor2D>) -> $physical<X> {
+                self.cast()
+            }
+        }
+
+        impl<P: Pixel, X: Pixel> ToLogical<X> for $physical<P> {
+            type Output = $logical<X>;
+
+            #[inline]
+            fn to_logical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> $logical<X> {
+                $physical::to_logical(self, Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+
+        impl<P: Pixel, X: Pixel> FromPhysical<$physical<X>> for $logical<P> {
+            #[inline]
+            fn from_physical(physical: $physical<X>, dpi_factor: impl Into<ScaleFactor2D>) -> Self {
+                physical.to_logical(Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+
+        impl<P: Pixel, X: Pixel> FromLogical<$logical<X>> for $physical<P> {
+            #[inline]
+            fn from_logical(logical: $logical<X>, dpi_factor: impl Into<ScaleFactor2D>) -> Self {
+                logical.to_physical(Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+    };
+}
+
+impl_conversion_traits!(LogicalPosition, PhysicalPosition);
+impl_conversion_traits!(LogicalDelta, PhysicalDelta);
+impl_conversion_traits!(LogicalSize, PhysicalSize);
+impl_conversion_traits!(LogicalRect, PhysicalRect);
+impl_conversion_traits!(LogicalInsets, PhysicalInsets);
+impl_conversion_traits!(LogicalUnit, PhysicalUnit, x);
+
+macro_rules! impl_enum_conversion_traits {
+    ($enum:ident, $logical:ident, $physical:ident $(, $axis:ident)?) => {
+        impl<X: Pixel> ToPhysical<X> for $enum {
+            type Output = $physical<X>;
+
+            #[inline]
+            fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> $physical<X> {
+                $enum::to_physical(self, Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+
+        impl<X: Pixel> ToLogical<X> for $enum {
+            type Output = $logical<X>;
+
+            #[inline]
+            fn to_logical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> $logical<X> {
+                $enum::to_logical(self, Into::<ScaleFactor2D>::into(dpi_factor)$(.$axis)?)
+            }
+        }
+    };
+}
+
+impl_enum_conversion_traits!(Position, LogicalPosition, PhysicalPosition);
+impl_enum_conversion_traits!(Size, LogicalSize, PhysicalSize);
+impl_enum_conversion_traits!(Rect, LogicalRect, PhysicalRect);
+impl_enum_conversion_traits!(Insets, LogicalInsets, PhysicalInsets);
+impl_enum_conversion_traits!(PixelUnit, LogicalUnit, PhysicalUnit, x);
+
+impl<X: Pixel, T: ToPhysical<X>> ToPhysical<X> for Option<T> {
+    type Output = Option<T::Output>;
+
+    #[inline]
+    fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output {
+        self.as_ref().map(|value| value.to_physical(dpi_factor))
+    }
+}
+
+impl<X: Pixel, T: ToLogical<X>> ToLogical<X> for Option<T> {
+    type Output = Option<T::Output>;
+
+    #[inline]
+    fn to_logical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output {
+        self.as_ref().map(|value| value.to_logical(dpi_factor))
+    }
+}
+
+impl<X: Pixel, T: ToPhysical<X>> ToPhysical<X> for [T] {
+    type Output = Vec<T::Output>;
+
+    fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output {
+        let dpi_factor = dpi_factor.into();
+        self.iter()
+            .map(|value| value.to_physical(dpi_factor))
+            .collect()
+    }
+}
+
+impl<X: Pixel, T: ToLogical<X>> ToLogical<X> for [T] {
+    type Output = Vec<T::Output>;
+
+    fn to_logical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output {
+        let dpi_factor = dpi_factor.into();
+        self.iter()
+            .map(|value| value.to_logical(dpi_factor))
+            .collect()
+    }
 }
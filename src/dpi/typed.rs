@@ -0,0 +1,937 @@
+//! Positions, deltas, sizes, rectangles and insets tagged with the unit they're measured in.
+//!
+//! [`LogicalPosition`], [`PhysicalSize`] and the other types in the parent module are aliases of
+//! the types here, using the built-in [`Logical`] and [`Physical`] units. Applications dealing
+//! with other coordinate spaces, e.g. texture texels or document points, can define units of their
+//! own so that the compiler catches values being mixed up, the same way it does for logical and
+//! physical pixels. Units are only ever used as type parameters, so an empty enum, like
+//! `enum Texel {}`, will do, along with an implementation of [`Unit`] naming it.
+//!
+//! Values can only be converted from one unit to another with a [`Scale`] between them. The
+//! arithmetic operators, the `cast` family of functions and the geometry operations work the same
+//! in every unit.
+//!
+//! [`Position`], [`Delta`], [`Size`] and [`Insets`] have a `unit` field holding their unit, so
+//! struct literals need a `unit: PhantomData`, and patterns matching their fields need a `..`,
+//! though `new` is usually simpler. [`Rect`] has no such field, as its position and size already
+//! carry the unit.
+//!
+//! [`LogicalPosition`]: ../type.LogicalPosition.html
+//! [`PhysicalSize`]: ../type.PhysicalSize.html
+//! [`Unit`]: trait.Unit.html
+//! [`Logical`]: enum.Logical.html
+//! [`Physical`]: enum.Physical.html
+//! [`Scale`]: struct.Scale.html
+//! [`Position`]: struct.Position.html
+//! [`Delta`]: struct.Delta.html
+//! [`Size`]: struct.Size.html
+//! [`Insets`]: struct.Insets.html
+//! [`Rect`]: struct.Rect.html
+
+use super::{max_pixel, min_pixel, Pixel, Rounding, ScaleFactor2D};
+use crate::error::Error;
+
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::{fmt, ops};
+
+/// The unit of logical pixels, which are physical pixels divided by the scale factor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Logical {}
+
+/// The unit of physical pixels, which correspond to the actual pixels on the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Physical {}
+
+/// A unit values can be measured in.
+///
+/// The name is used when debug printing, so that e.g. a `Position<f64, Logical>` prints as a
+/// `LogicalPosition`, matching its alias in the parent module.
+pub trait Unit {
+    const NAME: &'static str;
+}
+
+impl Unit for Logical {
+    const NAME: &'static str = "Logical";
+}
+
+impl Unit for Physical {
+    const NAME: &'static str = "Physical";
+}
+
+/// A position measured in `U`.
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Position<P, U> {
+    pub x: P,
+    pub y: P,
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
+    pub unit: PhantomData<U>,
+}
+
+/// A displacement between two positions, measured in `U`.
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Delta<P, U> {
+    pub x: P,
+    pub y: P,
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
+    pub unit: PhantomData<U>,
+}
+
+/// A size measured in `U`.
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Size<P, U> {
+    pub width: P,
+    pub height: P,
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
+    pub unit: PhantomData<U>,
+}
+
+/// A rectangle measured in `U`.
+///
+/// The rectangle spans from `pos` (inclusive) to `pos + size` (exclusive), with the origin in the
+/// top-left corner.
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(Serialize, Deserialize),
+    serde(bound(
+        serialize = "P: serde::Serialize",
+        deserialize = "P: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Rect<P, U> {
+    pub pos: Position<P, U>,
+    pub size: Size<P, U>,
+}
+
+/// The distances each edge of a rectangle is moved inwards by, measured in `U`, such as safe area
+/// insets, client-side decoration shadows or window frame extents.
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Insets<P, U> {
+    pub left: P,
+    pub top: P,
+    pub right: P,
+    pub bottom: P,
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
+    pub unit: PhantomData<U>,
+}
+
+impl<P, U> Position<P, U> {
+    #[inline]
+    pub const fn new(x: P, y: P) -> Self {
+        Position {
+            x,
+            y,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<P, U> Delta<P, U> {
+    #[inline]
+    pub const fn new(x: P, y: P) -> Self {
+        Delta {
+            x,
+            y,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<P, U> Size<P, U> {
+    #[inline]
+    pub const fn new(width: P, height: P) -> Self {
+        Size {
+            width,
+            height,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<P, U> Rect<P, U> {
+    #[inline]
+    pub const fn new(pos: Position<P, U>, size: Size<P, U>) -> Self {
+        Rect { pos, size }
+    }
+}
+
+impl<P, U> Insets<P, U> {
+    #[inline]
+    pub const fn new(left: P, top: P, right: P, bottom: P) -> Self {
+        Insets {
+            left,
+            top,
+            right,
+            bottom,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<P: Copy, U> Insets<P, U> {
+    /// The same inset on every edge.
+    #[inline]
+    pub fn uniform(inset: P) -> Self {
+        Insets::new(inset, inset, inset, inset)
+    }
+
+    /// The same inset on the left and right edges, and on the top and bottom edges.
+    #[inline]
+    pub fn symmetric(horizontal: P, vertical: P) -> Self {
+        Insets::new(horizontal, vertical, horizontal, vertical)
+    }
+}
+
+// The derives would require the unit to implement the traits too, which would make user-defined
+// units a chore to write.
+macro_rules! impl_unit_agnostic_traits {
+    ($ty:ident, $($field:ident),+) => {
+        impl<P: Clone, U> Clone for $ty<P, U> {
+            #[inline]
+            fn clone(&self) -> Self {
+                $ty::new($(self.$field.clone()),+)
+            }
+        }
+
+        impl<P: Copy, U> Copy for $ty<P, U> {}
+
+        impl<P: PartialEq, U> PartialEq for $ty<P, U> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&+
+            }
+        }
+
+        impl<P: Eq, U> Eq for $ty<P, U> {}
+
+        impl<P: Hash, U> Hash for $ty<P, U> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                $(self.$field.hash(state);)+
+            }
+        }
+
+        impl<P: fmt::Debug, U: Unit> fmt::Debug for $ty<P, U> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let name = format!("{}{}", U::NAME, stringify!($ty));
+                f.debug_struct(&name)
+                    $(.field(stringify!($field), &self.$field))+
+                    .finish()
+            }
+        }
+    };
+}
+
+impl_unit_agnostic_traits!(Position, x, y);
+impl_unit_agnostic_traits!(Delta, x, y);
+impl_unit_agnostic_traits!(Size, width, height);
+impl_unit_agnostic_traits!(Rect, pos, size);
+impl_unit_agnostic_traits!(Insets, left, top, right, bottom);
+
+impl<P: Pixel, U> Position<P, U> {
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> Position<X, U> {
+        Position::new(self.x.cast(), self.y.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`. [`Outward`] floors.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: ../enum.Rounding.html#variant.Outward
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> Position<X, U> {
+        let rounding = rounding.lower();
+        Position::new(self.x.cast_with(rounding), self.y.cast_with(rounding))
+    }
+
+    /// Like [`cast`], but returns an error if a component isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<Position<X, U>, Error> {
+        Ok(Position::new(self.x.try_cast()?, self.y.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps each component to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> Position<X, U> {
+        Position::new(self.x.saturating_cast(), self.y.saturating_cast())
+    }
+}
+
+impl<P: Pixel, U> Delta<P, U> {
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> Delta<X, U> {
+        Delta::new(self.x.cast(), self.y.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> Delta<X, U> {
+        Delta::new(self.x.cast_with(rounding), self.y.cast_with(rounding))
+    }
+
+    /// Like [`cast`], but returns an error if a component isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<Delta<X, U>, Error> {
+        Ok(Delta::new(self.x.try_cast()?, self.y.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps each component to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> Delta<X, U> {
+        Delta::new(self.x.saturating_cast(), self.y.saturating_cast())
+    }
+}
+
+impl<P: Pixel, U> Size<P, U> {
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> Size<X, U> {
+        Size::new(self.width.cast(), self.height.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`. [`Outward`] ceils.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: ../enum.Rounding.html#variant.Outward
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> Size<X, U> {
+        let rounding = rounding.upper();
+        Size::new(
+            self.width.cast_with(rounding),
+            self.height.cast_with(rounding),
+        )
+    }
+
+    /// Like [`cast`], but returns an error if a component isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<Size<X, U>, Error> {
+        Ok(Size::new(self.width.try_cast()?, self.height.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps each component to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> Size<X, U> {
+        Size::new(self.width.saturating_cast(), self.height.saturating_cast())
+    }
+
+    /// Returns the component-wise minimum of the two sizes.
+    #[inline]
+    pub fn min(&self, other: Size<P, U>) -> Size<P, U> {
+        Size::new(
+            min_pixel(self.width, other.width),
+            min_pixel(self.height, other.height),
+        )
+    }
+
+    /// Returns the component-wise maximum of the two sizes.
+    #[inline]
+    pub fn max(&self, other: Size<P, U>) -> Size<P, U> {
+        Size::new(
+            max_pixel(self.width, other.width),
+            max_pixel(self.height, other.height),
+        )
+    }
+
+    /// Clamps each component between those of `min` and `max`. If `min` is larger than `max`,
+    /// `max` wins.
+    #[inline]
+    pub fn clamp(&self, min: Size<P, U>, max: Size<P, U>) -> Size<P, U> {
+        self.max(min).min(max)
+    }
+
+    #[inline]
+    pub fn area(&self) -> f64 {
+        self.width.into() * self.height.into()
+    }
+
+    /// Returns `true` if either component is zero (or negative).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !(self.width.into() > 0.0 && self.height.into() > 0.0)
+    }
+
+    /// Returns the width divided by the height, or `None` if the size is empty.
+    #[inline]
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.width.into() / self.height.into())
+        }
+    }
+
+    /// Returns the size left once `insets` are taken off, e.g. the inner size of a window from its
+    /// outer size and frame extents. Components that would be negative become zero.
+    #[inline]
+    pub fn inset(&self, insets: Insets<P, U>) -> Size<P, U> {
+        let (width, height) = insets.sum();
+        Size::new(
+            (self.width.into() - width).max(0.0),
+            (self.height.into() - height).max(0.0),
+        )
+        .cast()
+    }
+
+    /// Returns the size with `insets` added, e.g. the outer size of a window from its inner size
+    /// and frame extents.
+    #[inline]
+    pub fn outset(&self, insets: Insets<P, U>) -> Size<P, U> {
+        let (width, height) = insets.sum();
+        Size::new(self.width.into() + width, self.height.into() + height).cast()
+    }
+
+    /// Returns the largest size with the same aspect ratio which fits within `bounds`, e.g. to
+    /// letterbox a video. Empty sizes scale to zero.
+    #[inline]
+    pub fn scale_to_fit(&self, bounds: Size<P, U>) -> Size<P, U> {
+        self.scale_by(|x, y| x.min(y), bounds)
+    }
+
+    /// Returns the smallest size with the same aspect ratio which covers `bounds`, e.g. to crop an
+    /// image to fill a window. Empty sizes scale to zero.
+    #[inline]
+    pub fn scale_to_fill(&self, bounds: Size<P, U>) -> Size<P, U> {
+        self.scale_by(|x, y| x.max(y), bounds)
+    }
+
+    #[inline]
+    fn scale_by<F: Fn(f64, f64) -> f64>(&self, pick: F, bounds: Size<P, U>) -> Size<P, U> {
+        if self.is_empty() {
+            return Size::new(0.0, 0.0).cast();
+        }
+        let (width, height) = (self.width.into(), self.height.into());
+        let scale = pick(bounds.width.into() / width, bounds.height.into() / height);
+        Size::new(width * scale, height * scale).cast()
+    }
+}
+
+impl<P: Pixel, U> Rect<P, U> {
+    #[inline]
+    pub(super) fn from_edges(left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        Rect::new(
+            Position::new(left, top).cast(),
+            Size::new(right - left, bottom - top).cast(),
+        )
+    }
+
+    #[inline]
+    pub(super) fn edges(&self) -> (f64, f64, f64, f64) {
+        let left = self.pos.x.into();
+        let top = self.pos.y.into();
+        (
+            left,
+            top,
+            left + self.size.width.into(),
+            top + self.size.height.into(),
+        )
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> Rect<X, U> {
+        Rect::new(self.pos.cast(), self.size.cast())
+    }
+
+    /// Like [`cast`], but rounds using `rounding`.
+    ///
+    /// The mode is applied to the edges of the rectangle rather than to its size, so rectangles
+    /// sharing an edge keep sharing it. With [`Outward`], the result always covers `self`.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: ../enum.Rounding.html#variant.Outward
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> Rect<X, U> {
+        let (left, top, right, bottom) = self.edges();
+        let (lower, upper) = (rounding.lower(), rounding.upper());
+        Rect::from_edges(
+            lower.apply(left),
+            lower.apply(top),
+            upper.apply(right),
+            upper.apply(bottom),
+        )
+    }
+
+    /// Like [`cast`], but returns an error if a component isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<Rect<X, U>, Error> {
+        Ok(Rect::new(self.pos.try_cast()?, self.size.try_cast()?))
+    }
+
+    /// Like [`cast`], but clamps each component to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> Rect<X, U> {
+        Rect::new(self.pos.saturating_cast(), self.size.saturating_cast())
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !(self.size.width.into() > 0.0 && self.size.height.into() > 0.0)
+    }
+
+    /// Returns `true` if `point` lies within the rectangle.
+    #[inline]
+    pub fn contains(&self, point: Position<P, U>) -> bool {
+        let (left, top, right, bottom) = self.edges();
+        let (x, y) = (point.x.into(), point.y.into());
+        x >= left && x < right && y >= top && y < bottom
+    }
+
+    /// Returns `true` if `other` lies entirely within the rectangle. Empty rectangles are never
+    /// contained.
+    #[inline]
+    pub fn contains_rect(&self, other: &Rect<P, U>) -> bool {
+        let (left, top, right, bottom) = self.edges();
+        let (o_left, o_top, o_right, o_bottom) = other.edges();
+        !other.is_empty()
+            && o_left >= left
+            && o_top >= top
+            && o_right <= right
+            && o_bottom <= bottom
+    }
+
+    /// Returns `true` if the two rectangles share a non-empty area.
+    #[inline]
+    pub fn intersects(&self, other: &Rect<P, U>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the area shared by both rectangles, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Rect<P, U>) -> Option<Rect<P, U>> {
+        let (left, top, right, bottom) = self.edges();
+        let (o_left, o_top, o_right, o_bottom) = other.edges();
+        let rect = Rect::from_edges(
+            left.max(o_left),
+            top.max(o_top),
+            right.min(o_right),
+            bottom.min(o_bottom),
+        );
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    /// Returns the smallest rectangle containing both rectangles. Empty rectangles are ignored.
+    pub fn union(&self, other: &Rect<P, U>) -> Rect<P, U> {
+        if other.is_empty() {
+            return *self;
+        } else if self.is_empty() {
+            return *other;
+        }
+        let (left, top, right, bottom) = self.edges();
+        let (o_left, o_top, o_right, o_bottom) = other.edges();
+        Rect::from_edges(
+            left.min(o_left),
+            top.min(o_top),
+            right.max(o_right),
+            bottom.max(o_bottom),
+        )
+    }
+
+    /// Returns the rectangle with each edge moved inwards by `insets`, e.g. the area of a surface
+    /// outside of the safe area insets. If the insets don't fit, the rectangle collapses to an
+    /// empty one rather than getting a negative size.
+    pub fn inset(&self, insets: Insets<P, U>) -> Rect<P, U> {
+        let (left, top, right, bottom) = self.edges();
+        let left = left + insets.left.into();
+        let top = top + insets.top.into();
+        Rect::from_edges(
+            left,
+            top,
+            (right - insets.right.into()).max(left),
+            (bottom - insets.bottom.into()).max(top),
+        )
+    }
+
+    /// Returns the rectangle with each edge moved outwards by `insets`, e.g. the area of a window
+    /// including its frame.
+    pub fn outset(&self, insets: Insets<P, U>) -> Rect<P, U> {
+        let (left, top, right, bottom) = self.edges();
+        Rect::from_edges(
+            left - insets.left.into(),
+            top - insets.top.into(),
+            right + insets.right.into(),
+            bottom + insets.bottom.into(),
+        )
+    }
+
+    /// Returns the rectangle moved by `delta`.
+    ///
+    /// The delta can use a different pixel type, so that e.g. a `Rect<u32, U>` can be moved left
+    /// or up by a `Delta<i32, U>`. Positions that don't fit in `P` are clamped.
+    #[inline]
+    pub fn translate<X: Pixel>(&self, delta: Delta<X, U>) -> Rect<P, U> {
+        let x = self.pos.x.into() + delta.x.into();
+        let y = self.pos.y.into() + delta.y.into();
+        Rect::new(Position::new(x, y).saturating_cast(), self.size)
+    }
+}
+
+impl<P: Pixel, U> Insets<P, U> {
+    /// The total horizontal and vertical insets.
+    #[inline]
+    fn sum(&self) -> (f64, f64) {
+        (
+            self.left.into() + self.right.into(),
+            self.top.into() + self.bottom.into(),
+        )
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> Insets<X, U> {
+        Insets::new(
+            self.left.cast(),
+            self.top.cast(),
+            self.right.cast(),
+            self.bottom.cast(),
+        )
+    }
+
+    /// Like [`cast`], but rounds using `rounding`. [`Outward`] ceils, so that rounded insets never
+    /// let content into the area they're keeping clear.
+    ///
+    /// [`cast`]: #method.cast
+    /// [`Outward`]: ../enum.Rounding.html#variant.Outward
+    #[inline]
+    pub fn cast_with<X: Pixel>(&self, rounding: Rounding) -> Insets<X, U> {
+        let rounding = rounding.upper();
+        Insets::new(
+            self.left.cast_with(rounding),
+            self.top.cast_with(rounding),
+            self.right.cast_with(rounding),
+            self.bottom.cast_with(rounding),
+        )
+    }
+
+    /// Like [`cast`], but returns an error if a component isn't finite or doesn't fit in `X`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn try_cast<X: Pixel>(&self) -> Result<Insets<X, U>, Error> {
+        Ok(Insets::new(
+            self.left.try_cast()?,
+            self.top.try_cast()?,
+            self.right.try_cast()?,
+            self.bottom.try_cast()?,
+        ))
+    }
+
+    /// Like [`cast`], but clamps each component to the range of `X`. NaN becomes `0`.
+    ///
+    /// [`cast`]: #method.cast
+    #[inline]
+    pub fn saturating_cast<X: Pixel>(&self) -> Insets<X, U> {
+        Insets::new(
+            self.left.saturating_cast(),
+            self.top.saturating_cast(),
+            self.right.saturating_cast(),
+            self.bottom.saturating_cast(),
+        )
+    }
+
+    /// Returns the size taken up by the insets, i.e. the sum of the left and right insets by the
+    /// sum of the top and bottom ones.
+    #[inline]
+    pub fn size(&self) -> Size<P, U> {
+        let (width, height) = self.sum();
+        Size::new(width, height).cast()
+    }
+}
+
+macro_rules! impl_pair_conversions {
+    ($ty:ident, $a:ident, $b:ident) => {
+        impl<P: Pixel, X: Pixel, U> From<(X, X)> for $ty<P, U> {
+            fn from((x, y): (X, X)) -> $ty<P, U> {
+                $ty::new(x.cast(), y.cast())
+            }
+        }
+
+        impl<P: Pixel, X: Pixel, U> From<$ty<P, U>> for (X, X) {
+            fn from(value: $ty<P, U>) -> (X, X) {
+                (value.$a.cast(), value.$b.cast())
+            }
+        }
+
+        impl<P: Pixel, X: Pixel, U> From<[X; 2]> for $ty<P, U> {
+            fn from([x, y]: [X; 2]) -> $ty<P, U> {
+                $ty::new(x.cast(), y.cast())
+            }
+        }
+
+        impl<P: Pixel, X: Pixel, U> From<$ty<P, U>> for [X; 2] {
+            fn from(value: $ty<P, U>) -> [X; 2] {
+                [value.$a.cast(), value.$b.cast()]
+            }
+        }
+    };
+}
+
+impl_pair_conversions!(Position, x, y);
+impl_pair_conversions!(Delta, x, y);
+impl_pair_conversions!(Size, width, height);
+
+impl<P: Pixel, X: Pixel, U> From<(X, X, X, X)> for Rect<P, U> {
+    fn from((x, y, width, height): (X, X, X, X)) -> Rect<P, U> {
+        Rect::new(
+            Position::new(x.cast(), y.cast()),
+            Size::new(width.cast(), height.cast()),
+        )
+    }
+}
+
+impl<P: Pixel, X: Pixel, U> From<Rect<P, U>> for (X, X, X, X) {
+    fn from(rect: Rect<P, U>) -> (X, X, X, X) {
+        (
+            rect.pos.x.cast(),
+            rect.pos.y.cast(),
+            rect.size.width.cast(),
+            rect.size.height.cast(),
+        )
+    }
+}
+
+/// A scale converting values measured in `Src` into values measured in `Dst`.
+///
+/// A `Scale<Logical, Physical>` does the same as passing its factor to `to_physical`, and its
+/// [`inverse`] the same as passing it to `to_logical`. Scales between other units compose with
+/// [`then`], so e.g. a `Scale<Texel, Logical>` followed by the window's `Scale<Logical, Physical>`
+/// maps texels straight to physical pixels.
+///
+/// [`inverse`]: #method.inverse
+/// [`then`]: #method.then
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Scale<Src, Dst> {
+    factor: ScaleFactor2D,
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
+    units: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<Src, Dst> Scale<Src, Dst> {
+    #[inline]
+    pub fn new(factor: impl Into<ScaleFactor2D>) -> Self {
+        Scale {
+            factor: factor.into(),
+            units: PhantomData,
+        }
+    }
+
+    /// The number of `Dst` units per `Src` unit on each axis.
+    #[inline]
+    pub fn factor(&self) -> ScaleFactor2D {
+        self.factor
+    }
+
+    /// Returns the scale converting back from `Dst` to `Src`. Exact factors stay exact.
+    #[inline]
+    pub fn inverse(&self) -> Scale<Dst, Src> {
+        Scale::new(ScaleFactor2D::new(
+            self.factor.x.recip(),
+            self.factor.y.recip(),
+        ))
+    }
+
+    /// Returns the scale applying this one, then `next`.
+    #[inline]
+    pub fn then<Next>(&self, next: &Scale<Dst, Next>) -> Scale<Src, Next> {
+        Scale::new(ScaleFactor2D::new(
+            self.factor.x.then(next.factor.x),
+            self.factor.y.then(next.factor.y),
+        ))
+    }
+
+    #[inline]
+    pub fn transform_position<P: Pixel, X: Pixel>(
+        &self,
+        position: Position<P, Src>,
+    ) -> Position<X, Dst> {
+        let x = self.factor.x.scale(position.x.into());
+        let y = self.factor.y.scale(position.y.into());
+        Position::new(x, y).cast()
+    }
+
+    #[inline]
+    pub fn transform_delta<P: Pixel, X: Pixel>(&self, delta: Delta<P, Src>) -> Delta<X, Dst> {
+        let x = self.factor.x.scale(delta.x.into());
+        let y = self.factor.y.scale(delta.y.into());
+        Delta::new(x, y).cast()
+    }
+
+    #[inline]
+    pub fn transform_size<P: Pixel, X: Pixel>(&self, size: Size<P, Src>) -> Size<X, Dst> {
+        let width = self.factor.x.scale(size.width.into());
+        let height = self.factor.y.scale(size.height.into());
+        Size::new(width, height).cast()
+    }
+
+    #[inline]
+    pub fn transform_rect<P: Pixel, X: Pixel>(&self, rect: Rect<P, Src>) -> Rect<X, Dst> {
+        Rect::new(
+            self.transform_position(rect.pos),
+            self.transform_size(rect.size),
+        )
+    }
+
+    #[inline]
+    pub fn transform_insets<P: Pixel, X: Pixel>(&self, insets: Insets<P, Src>) -> Insets<X, Dst> {
+        let (x, y) = (self.factor.x, self.factor.y);
+        Insets::new(
+            x.scale(insets.left.into()),
+            y.scale(insets.top.into()),
+            x.scale(insets.right.into()),
+            y.scale(insets.bottom.into()),
+        )
+        .cast()
+    }
+}
+
+impl<Src, Dst> Default for Scale<Src, Dst> {
+    #[inline]
+    fn default() -> Self {
+        Scale::new(ScaleFactor2D::IDENTITY)
+    }
+}
+
+impl<Src, Dst> Clone for Scale<Src, Dst> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Src, Dst> Copy for Scale<Src, Dst> {}
+
+impl<Src, Dst> PartialEq for Scale<Src, Dst> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.factor == other.factor
+    }
+}
+
+impl<Src: Unit, Dst: Unit> fmt::Debug for Scale<Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("Scale<{}, {}>", Src::NAME, Dst::NAME);
+        f.debug_struct(&name).field("factor", &self.factor).finish()
+    }
+}
+
+// Arithmetic. Values in different units can't be mixed; convert one to the other first.
+
+macro_rules! impl_binary_op {
+    ($op:ident, $fn:ident, $lhs:ident, $rhs:ident, $out:ident, $a:ident, $b:ident) => {
+        impl<P: ops::$op<Output = P>, U> ops::$op<$rhs<P, U>> for $lhs<P, U> {
+            type Output = $out<P, U>;
+
+            #[inline]
+            fn $fn(self, rhs: $rhs<P, U>) -> $out<P, U> {
+                $out::new(
+                    ops::$op::$fn(self.$a, rhs.$a),
+                    ops::$op::$fn(self.$b, rhs.$b),
+                )
+            }
+        }
+    };
+}
+
+macro_rules! impl_assign_op {
+    ($op:ident, $fn:ident, $lhs:ident, $rhs:ident, $a:ident, $b:ident) => {
+        impl<P: ops::$op, U> ops::$op<$rhs<P, U>> for $lhs<P, U> {
+            #[inline]
+            fn $fn(&mut self, rhs: $rhs<P, U>) {
+                ops::$op::$fn(&mut self.$a, rhs.$a);
+                ops::$op::$fn(&mut self.$b, rhs.$b);
+            }
+        }
+    };
+}
+
+macro_rules! impl_scalar_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident, $ty:ident, $a:ident, $b:ident) => {
+        impl<P: Copy + ops::$op<Output = P>, U> ops::$op<P> for $ty<P, U> {
+            type Output = $ty<P, U>;
+
+            #[inline]
+            fn $fn(self, rhs: P) -> $ty<P, U> {
+                $ty::new(ops::$op::$fn(self.$a, rhs), ops::$op::$fn(self.$b, rhs))
+            }
+        }
+
+        impl<P: Copy + ops::$assign, U> ops::$assign<P> for $ty<P, U> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: P) {
+                ops::$assign::$assign_fn(&mut self.$a, rhs);
+                ops::$assign::$assign_fn(&mut self.$b, rhs);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, Position, Delta, Position, x, y);
+impl_binary_op!(Sub, sub, Position, Delta, Position, x, y);
+impl_assign_op!(AddAssign, add_assign, Position, Delta, x, y);
+impl_assign_op!(SubAssign, sub_assign, Position, Delta, x, y);
+impl_binary_op!(Add, add, Delta, Delta, Delta, x, y);
+impl_binary_op!(Sub, sub, Delta, Delta, Delta, x, y);
+impl_assign_op!(AddAssign, add_assign, Delta, Delta, x, y);
+impl_assign_op!(SubAssign, sub_assign, Delta, Delta, x, y);
+impl_binary_op!(Add, add, Size, Size, Size, width, height);
+impl_binary_op!(Sub, sub, Size, Size, Size, width, height);
+impl_assign_op!(AddAssign, add_assign, Size, Size, width, height);
+impl_assign_op!(SubAssign, sub_assign, Size, Size, width, height);
+impl_scalar_op!(Mul, mul, MulAssign, mul_assign, Delta, x, y);
+impl_scalar_op!(Div, div, DivAssign, div_assign, Delta, x, y);
+impl_scalar_op!(Mul, mul, MulAssign, mul_assign, Size, width, height);
+impl_scalar_op!(Div, div, DivAssign, div_assign, Size, width, height);
+
+// Only signed pixels can be subtracted, as the delta between two unsigned positions may well be
+// negative. Unsigned positions have to be cast first.
+impl<P: ops::Sub<Output = P> + ops::Neg<Output = P>, U> ops::Sub for Position<P, U> {
+    type Output = Delta<P, U>;
+
+    #[inline]
+    fn sub(self, rhs: Position<P, U>) -> Delta<P, U> {
+        Delta::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<P: ops::Neg<Output = P>, U> ops::Neg for Delta<P, U> {
+    type Output = Delta<P, U>;
+
+    #[inline]
+    fn neg(self) -> Delta<P, U> {
+        Delta::new(-self.x, -self.y)
+    }
+}
+
+impl<P: ops::Add<Output = P>, U> ops::Add<Delta<P, U>> for Rect<P, U> {
+    type Output = Rect<P, U>;
+
+    #[inline]
+    fn add(self, rhs: Delta<P, U>) -> Rect<P, U> {
+        Rect::new(self.pos + rhs, self.size)
+    }
+}
+
+impl<P: ops::Sub<Output = P>, U> ops::Sub<Delta<P, U>> for Rect<P, U> {
+    type Output = Rect<P, U>;
+
+    #[inline]
+    fn sub(self, rhs: Delta<P, U>) -> Rect<P, U> {
+        Rect::new(self.pos - rhs, self.size)
+    }
+}
@@ -0,0 +1,276 @@
+//! A parser for EDID blobs, as exposed by RandR's `EDID` output property and by most other
+//! platforms in one way or another.
+//!
+//! The EDID is the most reliable source of a monitor's physical size, which is needed to pick a
+//! scale factor with [`scale_factor_from_mm`], and of its human readable name. EDID 1.3 and 1.4
+//! base blocks are supported, along with the detailed timings and short video descriptors of
+//! CEA-861 extension blocks. Other extension blocks are checksummed, then skipped.
+//!
+//! [`scale_factor_from_mm`]: ../dpi/fn.scale_factor_from_mm.html
+
+use crate::dpi::PhysicalSize;
+use crate::error::{Error, ErrorType};
+use crate::monitor::VideoMode;
+
+const BLOCK_LEN: usize = 128;
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+const CEA_EXTENSION_TAG: u8 = 0x02;
+
+/// A mode described by a detailed timing descriptor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct DetailedTiming {
+    /// The active resolution and refresh rate. For interlaced modes, the height is that of a
+    /// whole frame, while the refresh rate is that of a field.
+    pub mode: VideoMode,
+    pub pixel_clock_khz: u32,
+    /// The number of blanking pixels and lines.
+    pub blanking: PhysicalSize<u32>,
+    /// The size of the image in millimetres, if the descriptor provides one.
+    pub size_mm: Option<(u32, u32)>,
+    pub interlaced: bool,
+}
+
+/// The basics of a CEA-861 extension block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct CeaExtension {
+    pub revision: u8,
+    /// The monitor underscans IT formats by default.
+    pub underscan: bool,
+    pub basic_audio: bool,
+    /// The video identification codes of the modes listed in the short video descriptors, in
+    /// order of preference.
+    pub video_codes: Vec<u8>,
+}
+
+/// The information parsed from an EDID blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Edid {
+    /// The three letter PNP ID of the manufacturer, e.g. `"DEL"`.
+    pub manufacturer: String,
+    pub product_code: u16,
+    /// The numeric serial number, or `None` if it's unset.
+    pub serial_number: Option<u32>,
+    /// The serial number from the display descriptors, which is generally the one printed on the
+    /// monitor itself.
+    pub serial: Option<String>,
+    /// The monitor's name from the display descriptors.
+    pub product_name: Option<String>,
+    /// The EDID version and revision, e.g. `(1, 4)`.
+    pub version: (u8, u8),
+    /// The physical size of the image in millimetres, if known. EDID 1.4 allows only reporting
+    /// the aspect ratio, in which case this is `None`.
+    pub size_mm: Option<(u32, u32)>,
+    /// The detailed timings of the base block, followed by those of the CEA extension blocks.
+    /// The first one is the preferred mode.
+    pub detailed_timings: Vec<DetailedTiming>,
+    /// The first CEA-861 extension block, if any.
+    pub cea: Option<CeaExtension>,
+}
+
+#[inline]
+fn invalid(reason: &str) -> Error {
+    lmake_error!(ErrorType::InvalidEdid(reason.to_string()))
+}
+
+fn check_block(block: &[u8], index: usize) -> Result<(), Error> {
+    let sum = block.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    if sum == 0 {
+        Ok(())
+    } else {
+        Err(lmake_error!(ErrorType::EdidChecksumMismatch {
+            block: index
+        }))
+    }
+}
+
+fn decode_manufacturer(id: u16) -> String {
+    [(id >> 10) & 0x1F, (id >> 5) & 0x1F, id & 0x1F]
+        .iter()
+        .map(|&letter| match letter {
+            1..=26 => (b'A' + letter as u8 - 1) as char,
+            _ => '?',
+        })
+        .collect()
+}
+
+/// Decodes the text of a display descriptor, which is terminated by a newline and padded with
+/// spaces.
+fn decode_text(text: &[u8]) -> Option<String> {
+    let text: String = text
+        .iter()
+        .take_while(|&&byte| byte != b'\n')
+        .map(|&byte| byte as char)
+        .collect();
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// Parses an 18 byte detailed timing descriptor, or returns `None` if it's a display descriptor
+/// or padding.
+fn parse_detailed_timing(desc: &[u8]) -> Option<DetailedTiming> {
+    let pixel_clock_khz = u16::from_le_bytes([desc[0], desc[1]]) as u32 * 10;
+    if pixel_clock_khz == 0 {
+        return None;
+    }
+    let high = |byte: u8| (byte as u32 & 0xF0) << 4;
+    let low = |byte: u8| (byte as u32 & 0x0F) << 8;
+    let width = desc[2] as u32 | high(desc[4]);
+    let h_blank = desc[3] as u32 | low(desc[4]);
+    let height = desc[5] as u32 | high(desc[7]);
+    let v_blank = desc[6] as u32 | low(desc[7]);
+    let width_mm = desc[12] as u32 | high(desc[14]);
+    let height_mm = desc[13] as u32 | low(desc[14]);
+    let interlaced = desc[17] & 0x80 != 0;
+
+    let total = (width + h_blank) as u64 * (height + v_blank) as u64;
+    let refresh_rate_millihertz = (pixel_clock_khz as u64 * 1_000_000 + total / 2)
+        .checked_div(total)
+        .unwrap_or(0) as u32;
+    let height = if interlaced { height * 2 } else { height };
+
+    Some(DetailedTiming {
+        mode: VideoMode::new(PhysicalSize::new(width, height), refresh_rate_millihertz),
+        pixel_clock_khz,
+        blanking: PhysicalSize::new(h_blank, v_blank),
+        size_mm: if width_mm == 0 || height_mm == 0 {
+            None
+        } else {
+            Some((width_mm, height_mm))
+        },
+        interlaced,
+    })
+}
+
+fn parse_cea_extension(block: &[u8], detailed_timings: &mut Vec<DetailedTiming>) -> CeaExtension {
+    let mut cea = CeaExtension {
+        revision: block[1],
+        ..CeaExtension::default()
+    };
+    let dtd_offset = (block[2] as usize).min(BLOCK_LEN - 1);
+    if cea.revision >= 2 {
+        cea.underscan = block[3] & 0x80 != 0;
+        cea.basic_audio = block[3] & 0x40 != 0;
+    }
+
+    // Data blocks only exist since revision 3, and fill the space before the detailed timings.
+    if cea.revision >= 3 && dtd_offset > 4 {
+        let mut pos = 4;
+        while pos < dtd_offset {
+            let tag = block[pos] >> 5;
+            let len = (block[pos] & 0x1F) as usize;
+            let data = &block[(pos + 1).min(dtd_offset)..(pos + 1 + len).min(dtd_offset)];
+            // Video data block.
+            if tag == 2 {
+                cea.video_codes.extend(data.iter().map(|&svd| match svd {
+                    // The top bit marks native modes for the first 64 codes.
+                    129..=192 => svd & 0x7F,
+                    svd => svd,
+                }));
+            }
+            pos += 1 + len;
+        }
+    }
+
+    if dtd_offset >= 4 {
+        for desc in block[dtd_offset..BLOCK_LEN - 1].chunks_exact(18) {
+            match parse_detailed_timing(desc) {
+                Some(timing) => detailed_timings.push(timing),
+                // The rest of the block is padding.
+                None => break,
+            }
+        }
+    }
+    cea
+}
+
+impl Edid {
+    /// Parses an EDID blob, made up of a base block and any number of extension blocks.
+    ///
+    /// If the blob announces more extension blocks than it contains, only those present are
+    /// parsed, as some drivers only expose the base block.
+    pub fn parse(data: &[u8]) -> Result<Edid, Error> {
+        if data.len() < BLOCK_LEN {
+            return Err(invalid("the base block is truncated"));
+        }
+        let base = &data[..BLOCK_LEN];
+        if base[..8] != HEADER {
+            return Err(invalid("the header is missing"));
+        }
+        check_block(base, 0)?;
+        if base[18] != 1 {
+            return Err(invalid("only EDID 1.x is supported"));
+        }
+
+        let serial_number = u32::from_le_bytes([base[12], base[13], base[14], base[15]]);
+        let mut edid = Edid {
+            manufacturer: decode_manufacturer(u16::from_be_bytes([base[8], base[9]])),
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: if serial_number == 0 {
+                None
+            } else {
+                Some(serial_number)
+            },
+            serial: None,
+            product_name: None,
+            version: (base[18], base[19]),
+            size_mm: None,
+            detailed_timings: Vec::new(),
+            cea: None,
+        };
+
+        for desc in base[54..126].chunks_exact(18) {
+            if let Some(timing) = parse_detailed_timing(desc) {
+                edid.detailed_timings.push(timing);
+                continue;
+            }
+            match desc[3] {
+                0xFF => edid.serial = decode_text(&desc[5..]),
+                0xFC => edid.product_name = decode_text(&desc[5..]),
+                _ => (),
+            }
+        }
+
+        // The detailed timings have millimetre precision, while the base block only has
+        // centimetres.
+        let (width_cm, height_cm) = (base[21] as u32, base[22] as u32);
+        edid.size_mm = edid
+            .detailed_timings
+            .first()
+            .and_then(|timing| timing.size_mm)
+            .or(if width_cm == 0 || height_cm == 0 {
+                None
+            } else {
+                Some((width_cm * 10, height_cm * 10))
+            });
+
+        let extensions = base[126] as usize;
+        for (index, block) in data[BLOCK_LEN..]
+            .chunks_exact(BLOCK_LEN)
+            .take(extensions)
+            .enumerate()
+        {
+            check_block(block, index + 1)?;
+            if block[0] == CEA_EXTENSION_TAG {
+                let cea = parse_cea_extension(block, &mut edid.detailed_timings);
+                if edid.cea.is_none() {
+                    edid.cea = Some(cea);
+                }
+            }
+        }
+
+        Ok(edid)
+    }
+
+    /// Returns the preferred mode, which is the first detailed timing.
+    #[inline]
+    pub fn preferred_timing(&self) -> Option<&DetailedTiming> {
+        self.detailed_timings.first()
+    }
+}
@@ -147,6 +147,18 @@ pub enum ErrorType {
     /// The context you were using for this operation has been lost. This is
     /// generally non-recoverable.
     ContextLost,
+    /// The scale factor was not a normal positive number.
+    InvalidScaleFactor(f64),
+    /// A pixel value was not finite, or did not fit in the requested pixel
+    /// type once rounded.
+    InvalidPixelValue(f64),
+    /// An environment variable was set to a value that could not be
+    /// understood.
+    InvalidEnvVar { name: &'static str, value: String },
+    /// An EDID blob was truncated or otherwise malformed.
+    InvalidEdid(String),
+    /// The checksum of the given EDID block did not match its contents.
+    EdidChecksumMismatch { block: usize },
 
     /// Multiple errors happened.
     Multiple(Vec<Error>),
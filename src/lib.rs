@@ -2,6 +2,10 @@
 #[macro_use]
 extern crate serde;
 
-pub mod dpi;
+#[macro_use]
 pub mod error;
+pub mod dpi;
+pub mod edid;
+pub mod monitor;
 pub mod platform;
+pub mod region;
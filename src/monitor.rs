@@ -0,0 +1,223 @@
+//! Monitors and the virtual desktop they make up.
+//!
+//! Backends report every monitor's position and size in a single global physical coordinate space,
+//! the virtual desktop, but each monitor can have its own scale factor. [`DesktopLayout`] maps
+//! points and rectangles between that global space and the logical coordinates local to each
+//! monitor, which is what applications generally want to work with.
+//!
+//! Monitors don't have to touch: there can be gaps in the virtual desktop that no monitor covers.
+//! Points in those gaps are mapped using the nearest monitor.
+//!
+//! [`DesktopLayout`]: struct.DesktopLayout.html
+
+use crate::dpi::{
+    LogicalDelta, LogicalPosition, LogicalRect, PhysicalPosition, PhysicalRect, PhysicalSize,
+    ScaleFactor,
+};
+
+/// A description of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct MonitorInfo {
+    /// A human readable name, if the backend provides one.
+    pub name: Option<String>,
+    /// The position of the monitor's top-left corner in the virtual desktop.
+    pub position: PhysicalPosition<i32>,
+    /// The monitor's resolution.
+    pub size: PhysicalSize<u32>,
+    pub scale_factor: ScaleFactor,
+    /// The width and height of the monitor in millimetres, if known. Backends often report bogus
+    /// values, so don't rely on this.
+    pub size_mm: Option<(u32, u32)>,
+}
+
+impl MonitorInfo {
+    /// Returns the area covered by the monitor in the virtual desktop.
+    #[inline]
+    pub fn rect(&self) -> PhysicalRect<i32> {
+        PhysicalRect::new(self.position, self.size.cast())
+    }
+
+    /// Returns `true` if the global `point` lies on the monitor.
+    #[inline]
+    pub fn contains(&self, point: PhysicalPosition<i32>) -> bool {
+        self.rect().contains(point)
+    }
+
+    /// Converts a global physical point into logical coordinates relative to the monitor's
+    /// top-left corner. The point doesn't have to lie on the monitor.
+    #[inline]
+    pub fn to_local(&self, point: PhysicalPosition<i32>) -> LogicalPosition<f64> {
+        // The difference between two `i32`s doesn't always fit in one.
+        let delta = point.cast::<f64>() - self.position.cast::<f64>();
+        let delta = delta.to_logical::<f64>(self.scale_factor);
+        LogicalPosition::new(delta.x, delta.y)
+    }
+
+    /// Converts logical coordinates relative to the monitor's top-left corner into a global
+    /// physical point, clamped to the range of `i32`.
+    #[inline]
+    pub fn to_global(&self, point: LogicalPosition<f64>) -> PhysicalPosition<i32> {
+        let delta = LogicalDelta::new(point.x, point.y).to_physical::<f64>(self.scale_factor);
+        (self.position.cast::<f64>() + delta).cast()
+    }
+
+    /// Like [`to_local`], but for rectangles.
+    ///
+    /// [`to_local`]: #method.to_local
+    #[inline]
+    pub fn rect_to_local(&self, rect: PhysicalRect<i32>) -> LogicalRect<f64> {
+        LogicalRect::new(
+            self.to_local(rect.pos),
+            rect.size.to_logical(self.scale_factor),
+        )
+    }
+
+    /// Like [`to_global`], but for rectangles.
+    ///
+    /// [`to_global`]: #method.to_global
+    #[inline]
+    pub fn rect_to_global(&self, rect: LogicalRect<f64>) -> PhysicalRect<i32> {
+        PhysicalRect::new(
+            self.to_global(rect.pos),
+            rect.size.to_physical(self.scale_factor),
+        )
+    }
+
+    /// The squared distance from the monitor to the global `point`, or `0` if it's on the monitor.
+    fn distance_squared(&self, point: PhysicalPosition<i32>) -> i128 {
+        let rect = self.rect();
+        let axis = |value: i32, start: i32, length: i32| {
+            let (value, start, end) = (value as i64, start as i64, start as i64 + length as i64);
+            if value < start {
+                start - value
+            } else if value >= end {
+                value - end + 1
+            } else {
+                0
+            }
+        };
+        let dx = axis(point.x, rect.pos.x, rect.size.width);
+        let dy = axis(point.y, rect.pos.y, rect.size.height);
+        // Distances can get close to `u32::MAX`, whose square doesn't fit in an `i64`.
+        let (dx, dy) = (dx as i128, dy as i128);
+        dx * dx + dy * dy
+    }
+}
+
+/// A resolution and refresh rate a monitor can be driven at.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct VideoMode {
+    pub size: PhysicalSize<u32>,
+    pub refresh_rate_millihertz: u32,
+}
+
+impl VideoMode {
+    #[inline]
+    pub const fn new(size: PhysicalSize<u32>, refresh_rate_millihertz: u32) -> Self {
+        VideoMode {
+            size,
+            refresh_rate_millihertz,
+        }
+    }
+}
+
+/// The monitors making up the virtual desktop.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct DesktopLayout {
+    monitors: Vec<MonitorInfo>,
+}
+
+impl DesktopLayout {
+    #[inline]
+    pub fn new(monitors: Vec<MonitorInfo>) -> Self {
+        DesktopLayout { monitors }
+    }
+
+    #[inline]
+    pub fn monitors(&self) -> &[MonitorInfo] {
+        &self.monitors
+    }
+
+    /// Returns the smallest rectangle containing every monitor, or `None` if there are none.
+    pub fn bounds(&self) -> Option<PhysicalRect<i32>> {
+        let mut rects = self.monitors.iter().map(MonitorInfo::rect);
+        let first = rects.next()?;
+        Some(rects.fold(first, |acc, rect| acc.union(&rect)))
+    }
+
+    /// Returns the monitor containing the global `point`, if any.
+    #[inline]
+    pub fn monitor_at(&self, point: PhysicalPosition<i32>) -> Option<&MonitorInfo> {
+        self.monitors.iter().find(|monitor| monitor.contains(point))
+    }
+
+    /// Returns the monitor containing the global `point`, or the one closest to it if it's in a
+    /// gap between monitors. Only returns `None` if there are no monitors.
+    pub fn nearest_monitor(&self, point: PhysicalPosition<i32>) -> Option<&MonitorInfo> {
+        self.monitors
+            .iter()
+            .min_by_key(|monitor| monitor.distance_squared(point))
+    }
+
+    /// Returns the monitor sharing the largest area with `rect`, e.g. to decide which monitor's
+    /// scale factor a window should use. If `rect` doesn't overlap any monitor, the one nearest to
+    /// its center is returned. Only returns `None` if there are no monitors.
+    pub fn monitor_for_rect(&self, rect: PhysicalRect<i32>) -> Option<&MonitorInfo> {
+        let overlap = |monitor: &MonitorInfo| {
+            monitor
+                .rect()
+                .intersection(&rect)
+                .map(|area| area.size.width as i64 * area.size.height as i64)
+                .unwrap_or(0)
+        };
+        let mut best: Option<(&MonitorInfo, i64)> = None;
+        for monitor in &self.monitors {
+            let area = overlap(monitor);
+            if area > 0 && best.map(|(_, best_area)| area > best_area).unwrap_or(true) {
+                best = Some((monitor, area));
+            }
+        }
+        match best {
+            Some((monitor, _)) => Some(monitor),
+            None => {
+                let center = PhysicalPosition::new(
+                    rect.pos.x as f64 + (rect.size.width / 2) as f64,
+                    rect.pos.y as f64 + (rect.size.height / 2) as f64,
+                );
+                self.nearest_monitor(center.cast())
+            }
+        }
+    }
+
+    /// Converts a global physical point into logical coordinates local to the nearest monitor,
+    /// which is returned alongside them.
+    #[inline]
+    pub fn to_local(
+        &self,
+        point: PhysicalPosition<i32>,
+    ) -> Option<(&MonitorInfo, LogicalPosition<f64>)> {
+        self.nearest_monitor(point)
+            .map(|monitor| (monitor, monitor.to_local(point)))
+    }
+
+    /// Converts a global physical rectangle into logical coordinates local to the monitor it
+    /// overlaps most, which is returned alongside them.
+    #[inline]
+    pub fn rect_to_local(
+        &self,
+        rect: PhysicalRect<i32>,
+    ) -> Option<(&MonitorInfo, LogicalRect<f64>)> {
+        self.monitor_for_rect(rect)
+            .map(|monitor| (monitor, monitor.rect_to_local(rect)))
+    }
+}
+
+impl From<Vec<MonitorInfo>> for DesktopLayout {
+    #[inline]
+    fn from(monitors: Vec<MonitorInfo>) -> Self {
+        DesktopLayout::new(monitors)
+    }
+}
@@ -1,5 +1,7 @@
 pub use self::platform::*;
 
+pub mod xresources;
+
 #[cfg(target_os = "windows")]
 #[path = "platform/windows.rs"]
 mod platform;
@@ -0,0 +1,249 @@
+//! A parser for X resource databases, such as the contents of the root window's
+//! `RESOURCE_MANAGER` property, which is where `Xft.dpi`, `Xcursor.size` and `Xcursor.theme` live.
+//!
+//! This works on plain strings and doesn't need a connection to the X server, so it's available
+//! on every platform, and can be used, and tested, on headless machines. It follows the syntax and
+//! matching rules of Xlib's resource manager (`Xrm`), including tight (`.`) and loose (`*`)
+//! bindings, the `?` wildcard, class matching, `!` comments, line continuations and escape
+//! sequences in values.
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Binding {
+    Tight,
+    Loose,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    components: Vec<(Binding, String)>,
+    value: String,
+}
+
+/// How well a single level of a query was matched, ordered by Xrm's precedence rules: an entry
+/// matching the level beats one skipping it with a loose binding, then names beat classes which
+/// beat `?`, then tight bindings beat loose ones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LevelMatch {
+    Skipped,
+    Matched { kind: u8, tight: bool },
+}
+
+/// An X resource database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XResources {
+    entries: Vec<Entry>,
+}
+
+/// Splits `data` into logical lines, joining lines ending in a backslash with the next one.
+/// Other escape sequences are left for `unescape_value` to deal with.
+fn logical_lines(data: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => (),
+                Some(next) => {
+                    line.push('\\');
+                    line.push(next);
+                }
+                None => line.push('\\'),
+            },
+            '\n' => lines.push(std::mem::take(&mut line)),
+            c => line.push(c),
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+fn unescape_value(value: &str) -> String {
+    // Octal escapes stand for single bytes, several of which can make up one UTF-8 character, so
+    // the value is only decoded once they've all been unescaped.
+    let mut unescaped = Vec::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('n') => {
+                chars.next();
+                unescaped.push(b'\n');
+            }
+            Some(next @ ' ') | Some(next @ '\t') | Some(next @ '\\') => {
+                chars.next();
+                unescaped.push(next as u8);
+            }
+            Some('0'..='7') => {
+                let digits: String = chars.clone().take(3).collect();
+                if digits.len() == 3 && digits.chars().all(|d| ('0'..='7').contains(&d)) {
+                    unescaped.push(u32::from_str_radix(&digits, 8).unwrap_or(0) as u8);
+                    chars.nth(2);
+                } else {
+                    unescaped.push(b'\\');
+                }
+            }
+            _ => unescaped.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Parses a resource specification like `*Foo.bar`. Returns `None` if it's malformed.
+fn parse_specifier(specifier: &str) -> Option<Vec<(Binding, String)>> {
+    let mut components = Vec::new();
+    let mut binding = Binding::Tight;
+    let mut component = String::new();
+    for c in specifier.chars() {
+        match c {
+            '.' | '*' => {
+                if !component.is_empty() {
+                    components.push((binding, std::mem::take(&mut component)));
+                    binding = Binding::Tight;
+                }
+                if c == '*' {
+                    binding = Binding::Loose;
+                }
+            }
+            c if c.is_whitespace() => return None,
+            c => component.push(c),
+        }
+    }
+    if component.is_empty() {
+        return None;
+    }
+    components.push((binding, component));
+    Some(components)
+}
+
+/// Returns the best way `components` can match the query, level by level, or `None` if it can't.
+fn match_levels(
+    components: &[(Binding, String)],
+    names: &[&str],
+    classes: &[&str],
+) -> Option<Vec<LevelMatch>> {
+    let (&(binding, ref component), rest) = match components.split_first() {
+        Some(split) => split,
+        None if names.is_empty() => return Some(Vec::new()),
+        None => return None,
+    };
+    if names.is_empty() {
+        return None;
+    }
+
+    let kind = if component == names[0] {
+        Some(3)
+    } else if component == classes[0] {
+        Some(2)
+    } else if component == "?" {
+        Some(1)
+    } else {
+        None
+    };
+    let matched = kind.and_then(|kind| {
+        let mut levels = match_levels(rest, &names[1..], &classes[1..])?;
+        let tight = binding == Binding::Tight;
+        levels.insert(0, LevelMatch::Matched { kind, tight });
+        Some(levels)
+    });
+    let skipped = if binding == Binding::Loose {
+        match_levels(components, &names[1..], &classes[1..]).map(|mut levels| {
+            levels.insert(0, LevelMatch::Skipped);
+            levels
+        })
+    } else {
+        None
+    };
+    match (matched, skipped) {
+        (Some(matched), Some(skipped)) => Some(matched.max(skipped)),
+        (matched, skipped) => matched.or(skipped),
+    }
+}
+
+impl XResources {
+    /// Parses a resource database. Malformed lines are ignored, like Xlib does.
+    pub fn parse(data: &str) -> Self {
+        let mut resources = XResources::default();
+        for line in logical_lines(data) {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+                continue;
+            }
+            let colon = match line.find(':') {
+                Some(colon) => colon,
+                None => continue,
+            };
+            let components = match parse_specifier(line[..colon].trim_end()) {
+                Some(components) => components,
+                None => continue,
+            };
+            let value = unescape_value(line[colon + 1..].trim_start_matches(&[' ', '\t'][..]));
+            resources.insert(components, value);
+        }
+        resources
+    }
+
+    fn insert(&mut self, components: Vec<(Binding, String)>, value: String) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.components == components)
+        {
+            Some(entry) => entry.value = value,
+            None => self.entries.push(Entry { components, value }),
+        }
+    }
+
+    /// Looks up a resource by its fully qualified name and class, e.g. `"xterm.vt100.font"` and
+    /// `"XTerm.VT100.Font"`, returning the value of the most specific matching entry.
+    ///
+    /// Returns `None` if nothing matches, or if `name` and `class` don't have the same number of
+    /// components.
+    pub fn get(&self, name: &str, class: &str) -> Option<&str> {
+        let names: Vec<&str> = name.split('.').collect();
+        let classes: Vec<&str> = class.split('.').collect();
+        if names.len() != classes.len() || names.iter().chain(&classes).any(|c| c.is_empty()) {
+            return None;
+        }
+
+        let mut best: Option<(Vec<LevelMatch>, &Entry)> = None;
+        for entry in &self.entries {
+            if let Some(levels) = match_levels(&entry.components, &names, &classes) {
+                let better = match best {
+                    Some((ref best_levels, _)) => levels.cmp(best_levels) != Ordering::Less,
+                    None => true,
+                };
+                if better {
+                    best = Some((levels, entry));
+                }
+            }
+        }
+        best.map(|(_, entry)| entry.value.as_str())
+    }
+
+    /// The DPI fonts should be rendered at, which is also used to pick the scale factor.
+    #[inline]
+    pub fn xft_dpi(&self) -> Option<f64> {
+        self.get("Xft.dpi", "Xft.Dpi")?.trim().parse().ok()
+    }
+
+    /// The size cursors should be drawn at, in pixels.
+    #[inline]
+    pub fn xcursor_size(&self) -> Option<u32> {
+        self.get("Xcursor.size", "Xcursor.Size")?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// The name of the cursor theme to use.
+    #[inline]
+    pub fn xcursor_theme(&self) -> Option<&str> {
+        self.get("Xcursor.theme", "Xcursor.Theme").map(str::trim)
+    }
+}
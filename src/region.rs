@@ -0,0 +1,191 @@
+//! A collection of physical rectangles, such as the buffer damage passed to
+//! `swap_buffers_with_damage`.
+//!
+//! Damage tends to be reported in many small, often overlapping, pieces. Submitting all of them
+//! individually is wasteful, so [`Region`] can coalesce rectangles which merge without covering any
+//! extra pixels, clamp them to the surface, and, if the backend can only accept a handful of
+//! rectangles, greedily merge the pairs which waste the least area until it fits.
+//!
+//! [`Region`]: struct.Region.html
+
+use crate::dpi::{Origin, PhysicalPosition, PhysicalRect, PhysicalSize};
+
+use std::iter::FromIterator;
+use std::slice;
+
+/// A set of physical rectangles, in top-left origin surface coordinates.
+///
+/// Empty rectangles are never stored.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde_feature", derive(Serialize, Deserialize))]
+pub struct Region {
+    rects: Vec<PhysicalRect<i32>>,
+}
+
+#[inline]
+fn area(rect: &PhysicalRect<i32>) -> i64 {
+    if rect.is_empty() {
+        0
+    } else {
+        rect.size.width as i64 * rect.size.height as i64
+    }
+}
+
+/// The number of pixels covered by the union of `a` and `b` which are covered by neither.
+#[inline]
+fn merge_cost(a: &PhysicalRect<i32>, b: &PhysicalRect<i32>) -> i64 {
+    let covered = area(a) + area(b) - a.intersection(b).map(|i| area(&i)).unwrap_or(0);
+    area(&a.union(b)) - covered
+}
+
+impl Region {
+    #[inline]
+    pub fn new() -> Self {
+        Region { rects: Vec::new() }
+    }
+
+    /// Adds `rect` to the region. Empty rectangles are ignored.
+    #[inline]
+    pub fn push(&mut self, rect: PhysicalRect<i32>) {
+        if !rect.is_empty() {
+            self.rects.push(rect);
+        }
+    }
+
+    #[inline]
+    pub fn rects(&self) -> &[PhysicalRect<i32>] {
+        &self.rects
+    }
+
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, PhysicalRect<i32>> {
+        self.rects.iter()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.rects.clear()
+    }
+
+    /// Returns the smallest rectangle containing every rectangle in the region, or `None` if the
+    /// region is empty.
+    pub fn bounding_box(&self) -> Option<PhysicalRect<i32>> {
+        let mut rects = self.rects.iter();
+        let first = *rects.next()?;
+        Some(rects.fold(first, |acc, rect| acc.union(rect)))
+    }
+
+    /// Merges every pair of rectangles whose union covers exactly the same pixels as the pair
+    /// itself, e.g. rectangles contained in one another, or adjacent rectangles sharing a whole
+    /// edge. The covered area is left unchanged.
+    pub fn coalesce(&mut self) {
+        'restart: loop {
+            for i in 0..self.rects.len() {
+                for j in i + 1..self.rects.len() {
+                    if merge_cost(&self.rects[i], &self.rects[j]) == 0 {
+                        let other = self.rects.swap_remove(j);
+                        self.rects[i] = self.rects[i].union(&other);
+                        continue 'restart;
+                    }
+                }
+            }
+            break;
+        }
+    }
+
+    /// Clips every rectangle to a surface of the given size, dropping those which fall entirely
+    /// outside of it.
+    pub fn clamp(&mut self, surface: PhysicalSize<u32>) {
+        let bounds = PhysicalRect::new(PhysicalPosition::new(0, 0), surface.cast());
+        self.rects = self
+            .rects
+            .iter()
+            .filter_map(|rect| rect.intersection(&bounds))
+            .collect();
+    }
+
+    /// Returns the rectangles as a flat `[x, y, width, height, ...]` array, relative to the
+    /// `origin` corner of a surface of the given size.
+    ///
+    /// This is the layout expected by `eglSwapBuffersWithDamageKHR` and
+    /// `eglSetDamageRegionKHR`, which want a [`BottomLeft`] origin.
+    ///
+    /// [`BottomLeft`]: ../dpi/enum.Origin.html#variant.BottomLeft
+    pub fn flatten(&self, origin: Origin, surface: PhysicalSize<u32>) -> Vec<i32> {
+        let mut flat = Vec::with_capacity(self.rects.len() * 4);
+        for rect in &self.rects {
+            let rect = rect.change_origin(Origin::TopLeft, origin, surface);
+            flat.extend_from_slice(&[rect.pos.x, rect.pos.y, rect.size.width, rect.size.height]);
+        }
+        flat
+    }
+
+    /// Reduces the region to at most `max_rects` rectangles.
+    ///
+    /// The region is first coalesced, then the pair of rectangles whose union would cover the
+    /// fewest extra pixels is repeatedly merged. The resulting region always covers the original
+    /// one. A `max_rects` of `0` is treated as `1`.
+    pub fn limit(&mut self, max_rects: usize) {
+        let max_rects = max_rects.max(1);
+        self.coalesce();
+        while self.rects.len() > max_rects {
+            let mut best = (0, 1, i64::MAX);
+            for i in 0..self.rects.len() {
+                for j in i + 1..self.rects.len() {
+                    let cost = merge_cost(&self.rects[i], &self.rects[j]);
+                    if cost < best.2 {
+                        best = (i, j, cost);
+                    }
+                }
+            }
+            let (i, j, _) = best;
+            let other = self.rects.swap_remove(j);
+            self.rects[i] = self.rects[i].union(&other);
+        }
+    }
+}
+
+impl From<PhysicalRect<i32>> for Region {
+    #[inline]
+    fn from(rect: PhysicalRect<i32>) -> Region {
+        let mut region = Region::new();
+        region.push(rect);
+        region
+    }
+}
+
+impl Extend<PhysicalRect<i32>> for Region {
+    fn extend<I: IntoIterator<Item = PhysicalRect<i32>>>(&mut self, iter: I) {
+        for rect in iter {
+            self.push(rect);
+        }
+    }
+}
+
+impl FromIterator<PhysicalRect<i32>> for Region {
+    fn from_iter<I: IntoIterator<Item = PhysicalRect<i32>>>(iter: I) -> Region {
+        let mut region = Region::new();
+        region.extend(iter);
+        region
+    }
+}
+
+impl<'a> IntoIterator for &'a Region {
+    type Item = &'a PhysicalRect<i32>;
+    type IntoIter = slice::Iter<'a, PhysicalRect<i32>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.rects.iter()
+    }
+}
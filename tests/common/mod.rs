@@ -0,0 +1,5 @@
+use winit_types::dpi::PhysicalRect;
+
+pub fn prect(x: i32, y: i32, width: i32, height: i32) -> PhysicalRect<i32> {
+    (x, y, width, height).into()
+}
@@ -0,0 +1,1039 @@
+mod common;
+
+use common::prect;
+use std::marker::PhantomData;
+use winit_types::dpi::typed::{Logical, Physical, Scale, Unit};
+use winit_types::dpi::{
+    resolve_x11_scale_factor, scale_factor_from_mm, size_for_scale_factor_change, typed,
+    AffineTransform, FractionalScale, FromLogical, FromPhysical, Insets, LogicalDelta,
+    LogicalInsets, LogicalPosition, LogicalRect, LogicalSize, LogicalUnit, Origin, PhysicalDelta,
+    PhysicalInsets, PhysicalPosition, PhysicalRect, PhysicalSize, PhysicalUnit, Pixel, PixelUnit,
+    Position, Rect, Rounding, ScaleFactor, ScaleFactor2D, ScaleFactorSnapping, Size,
+    SizeConstraints, ToLogical, ToPhysical, Transform, X11ScaleFactorInputs,
+};
+use winit_types::error::ErrorType;
+
+#[test]
+fn rect_intersection_and_union() {
+    let a = prect(0, 0, 10, 10);
+    let b = prect(5, 5, 10, 10);
+    assert_eq!(a.intersection(&b), Some(prect(5, 5, 5, 5)));
+    assert_eq!(a.union(&b), prect(0, 0, 15, 15));
+
+    let c = prect(10, 0, 5, 5);
+    assert!(!a.intersects(&c));
+    assert_eq!(a.intersection(&c), None);
+    assert_eq!(a.union(&prect(3, 3, 0, 0)), a);
+}
+
+#[test]
+fn rect_containment() {
+    let a = prect(-5, -5, 10, 10);
+    assert!(a.contains(PhysicalPosition::new(-5, -5)));
+    assert!(!a.contains(PhysicalPosition::new(5, 0)));
+    assert!(a.contains_rect(&prect(0, 0, 5, 5)));
+    assert!(!a.contains_rect(&prect(0, 0, 6, 5)));
+    assert!(prect(0, 0, 0, 3).is_empty());
+    assert_eq!(
+        a.translate(PhysicalDelta::new(5, -5)),
+        prect(0, -10, 10, 10)
+    );
+    let unsigned: PhysicalRect<u32> = (10, 10, 5, 5).into();
+    assert_eq!(
+        unsigned.translate(PhysicalDelta::new(-4, -12)),
+        (6, 0, 5, 5).into()
+    );
+}
+
+#[test]
+fn rect_conversion() {
+    let logical = LogicalRect::new(LogicalPosition::new(1.5, 2.0), LogicalSize::new(10.0, 4.0));
+    assert_eq!(logical.to_physical::<i32>(2.0), prect(3, 4, 20, 8));
+    assert_eq!(prect(3, 4, 20, 8).to_logical::<f64>(2.0), logical);
+}
+
+#[test]
+fn rect_change_origin() {
+    let surface = PhysicalSize::new(100, 50);
+    let top_row = prect(10, 0, 20, 1);
+    let flipped = top_row.change_origin(Origin::TopLeft, Origin::BottomLeft, surface);
+    assert_eq!(flipped, prect(10, 49, 20, 1));
+    assert_eq!(
+        flipped.change_origin(Origin::BottomLeft, Origin::TopLeft, surface),
+        top_row
+    );
+    assert_eq!(
+        PhysicalPosition::new(3, 10).change_origin(Origin::TopLeft, Origin::BottomLeft, surface),
+        PhysicalPosition::new(3, 40)
+    );
+    // Rectangles sticking out of the surface keep their size.
+    assert_eq!(
+        prect(10, 45, 20, 10).change_origin(Origin::TopLeft, Origin::BottomLeft, surface),
+        prect(10, -5, 20, 10)
+    );
+
+    let logical = Rect::from(LogicalRect::new(
+        LogicalPosition::new(5.0, 0.0),
+        LogicalSize::new(10.0, 0.5),
+    ));
+    let flipped = logical.change_origin(Origin::TopLeft, Origin::BottomLeft, surface.into(), 2.0);
+    assert_eq!(
+        flipped,
+        Rect::from(LogicalRect::new(
+            LogicalPosition::new(5.0, 24.5),
+            LogicalSize::new(10.0, 0.5),
+        ))
+    );
+    assert_eq!(
+        flipped.change_origin(Origin::BottomLeft, Origin::TopLeft, surface.into(), 2.0),
+        logical
+    );
+    assert_eq!(
+        Rect::from(top_row).change_origin(
+            Origin::TopLeft,
+            Origin::BottomLeft,
+            LogicalSize::new(50.0, 25.0).into(),
+            2.0,
+        ),
+        Rect::from(prect(10, 49, 20, 1))
+    );
+    assert_eq!(
+        Position::from(LogicalPosition::new(1.5, 5.0)).change_origin(
+            Origin::TopLeft,
+            Origin::BottomLeft,
+            surface.into(),
+            2.0,
+        ),
+        Position::from(LogicalPosition::new(1.5, 20.0))
+    );
+}
+
+#[test]
+fn scale_factor_validation() {
+    assert!(ScaleFactor::new(0.0).is_err());
+    assert!(ScaleFactor::new(-1.0).is_err());
+    assert!(ScaleFactor::new(f64::NAN).is_err());
+
+    let scale = ScaleFactor::new(1.5).unwrap();
+    assert_eq!(
+        LogicalSize::new(10.0, 20.0).to_physical::<u32>(scale),
+        LogicalSize::new(10.0, 20.0).to_physical::<u32>(1.5)
+    );
+}
+
+#[test]
+#[should_panic]
+fn scale_factor_f64_shim_panics() {
+    LogicalPosition::new(1.0, 1.0).to_physical::<i32>(0.0);
+}
+
+#[test]
+fn try_conversions() {
+    let size = PhysicalSize::new(200u32, 100);
+    assert_eq!(
+        size.try_to_logical::<f64>(2.0).unwrap(),
+        LogicalSize::new(100.0, 50.0)
+    );
+    assert!(size.try_to_logical::<f64>(0.0).is_err());
+    assert!(LogicalSize::<f64>::try_from_physical(size, -2.0).is_err());
+    assert!(Size::from(size).try_to_logical::<f64>(0.0).is_err());
+    assert_eq!(
+        Position::from(LogicalPosition::new(1.0, 2.0))
+            .try_to_physical::<i32>(3.0)
+            .unwrap(),
+        PhysicalPosition::new(3, 6)
+    );
+
+    // They take the same scale factors as the infallible conversions.
+    let scale = ScaleFactor2D::try_new(2.0, 4.0).unwrap();
+    assert_eq!(
+        size.try_to_logical::<f64>(scale).unwrap(),
+        LogicalSize::new(100.0, 25.0)
+    );
+    assert_eq!(
+        Rect::from(prect(0, 0, 10, 10))
+            .try_to_logical::<f64>(ScaleFactor::from_ratio(5, 4).unwrap())
+            .unwrap(),
+        LogicalRect::new(LogicalPosition::new(0.0, 0.0), LogicalSize::new(8.0, 8.0))
+    );
+    assert_eq!(
+        LogicalUnit::new(2.0)
+            .try_to_physical::<u32>(FractionalScale::new(180).unwrap())
+            .unwrap(),
+        PhysicalUnit::new(3)
+    );
+    assert!(PixelUnit::from(PhysicalUnit::new(3))
+        .try_to_logical::<f64>(-1.0)
+        .is_err());
+
+    match size.try_to_logical::<f64>(f64::NAN).unwrap_err().ty {
+        ErrorType::InvalidScaleFactor(_) => (),
+        ty => panic!("unexpected error: {:?}", ty),
+    }
+    // Results that don't fit in the pixel type are errors rather than being saturated.
+    match LogicalSize::new(3e9, 1.0)
+        .try_to_physical::<i32>(1.0)
+        .unwrap_err()
+        .ty
+    {
+        ErrorType::InvalidPixelValue(_) => (),
+        ty => panic!("unexpected error: {:?}", ty),
+    }
+    let rect: LogicalRect<f64> = (0.0, 0.0, 200.0, 10.0).into();
+    assert!(Rect::from(rect).try_to_physical::<u8>(2.0).is_err());
+}
+
+#[test]
+fn checked_and_saturating_casts() {
+    assert_eq!(Pixel::try_cast::<u8>(255.4f64).unwrap(), 255);
+    assert!(Pixel::try_cast::<u8>(255.5f64).is_err());
+    assert!(Pixel::try_cast::<u32>(-1.0f64).is_err());
+    assert!(Pixel::try_cast::<i32>(f64::NAN).is_err());
+    assert!(Pixel::try_cast::<f32>(1e300f64).is_err());
+    assert_eq!(Pixel::saturating_cast::<i8>(1000.0f64), 127);
+    assert_eq!(Pixel::saturating_cast::<f32>(-1e300f64), f32::MIN);
+    assert_eq!(Pixel::saturating_cast::<f64>(f64::NAN), 0.0);
+
+    let position = LogicalPosition::new(-10.0, 20.0);
+    assert!(position.try_cast::<u32>().is_err());
+    assert_eq!(
+        position.try_cast::<i32>().unwrap(),
+        LogicalPosition::new(-10, 20)
+    );
+    assert_eq!(
+        position.saturating_cast::<u32>(),
+        LogicalPosition::new(0, 20)
+    );
+}
+
+#[test]
+fn rounding_modes() {
+    assert_eq!(Rounding::RoundHalfEven.apply(2.5), 2.0);
+    assert_eq!(Rounding::RoundHalfEven.apply(-3.5), -4.0);
+    assert_eq!(Rounding::Round.apply(2.5), 3.0);
+    assert_eq!(Pixel::cast_with::<i32>(-1.5f64, Rounding::Floor), -2);
+
+    let position = LogicalPosition::new(1.6, 2.4);
+    assert_eq!(
+        position.to_physical_with::<i32>(1.0, Rounding::Outward),
+        PhysicalPosition::new(1, 2)
+    );
+    assert_eq!(
+        LogicalSize::new(1.6, 2.4).to_physical_with::<u32>(1.0, Rounding::Outward),
+        PhysicalSize::new(2, 3)
+    );
+}
+
+#[test]
+fn rect_outward_covers_logical_area() {
+    // At 1.25, [1, 3) maps to [1.25, 3.75), which rounds to [1, 4) when snapping outward.
+    let logical = LogicalRect::new(LogicalPosition::new(1.0, 1.0), LogicalSize::new(2.0, 2.0));
+    assert_eq!(logical.to_physical::<i32>(1.25), prect(1, 1, 3, 3));
+    assert_eq!(logical.to_physical_outward::<i32>(1.25), prect(1, 1, 3, 3));
+
+    let logical = LogicalRect::new(LogicalPosition::new(0.5, 0.5), LogicalSize::new(1.0, 1.0));
+    assert_eq!(logical.to_physical::<i32>(1.25), prect(1, 1, 1, 1));
+    assert_eq!(logical.to_physical_outward::<i32>(1.25), prect(0, 0, 2, 2));
+}
+
+#[test]
+fn snap_to_pixels() {
+    assert_eq!(
+        LogicalPosition::new(1.1, 0.4).snap_to_pixels(1.5),
+        LogicalPosition::new(4.0 / 3.0, 2.0 / 3.0)
+    );
+    assert_eq!(
+        LogicalSize::new(10.1, 3.0).snap_to_pixels(2.0),
+        LogicalSize::new(10.0, 3.0)
+    );
+
+    // Three adjacent boxes of equal logical width stay adjacent once snapped.
+    let dpi_factor = 1.5;
+    let boxes: Vec<_> = (0..3)
+        .map(|i| {
+            LogicalRect::new(
+                LogicalPosition::new(i as f64 * 3.3, 0.0),
+                LogicalSize::new(3.3, 1.0),
+            )
+            .snap_to_pixels(dpi_factor)
+        })
+        .collect();
+    for pair in boxes.windows(2) {
+        assert_eq!(pair[0].pos.x + pair[0].size.width, pair[1].pos.x);
+    }
+    for b in &boxes {
+        let physical = b.to_physical::<f64>(dpi_factor);
+        assert!((physical.pos.x - physical.pos.x.round()).abs() < 1e-9);
+        assert!((physical.size.width - physical.size.width.round()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let a = PhysicalPosition::new(10, 20);
+    let b = PhysicalPosition::new(4, 25);
+    let delta = a - b;
+    assert_eq!(delta, PhysicalDelta::new(6, -5));
+    assert_eq!(b + delta, a);
+    assert_eq!(a - delta, b);
+    assert_eq!(-delta * 2, PhysicalDelta::new(-12, 10));
+
+    // Unsigned positions can't be subtracted directly, as the delta could be negative.
+    let (a, b) = (
+        PhysicalPosition::new(4u32, 25),
+        PhysicalPosition::new(10u32, 20),
+    );
+    assert_eq!(a.cast::<i32>() - b.cast::<i32>(), PhysicalDelta::new(-6, 5));
+
+    let mut position = LogicalPosition::new(1.0, 1.0);
+    position += LogicalDelta::new(0.5, -1.0);
+    assert_eq!(position, LogicalPosition::new(1.5, 0.0));
+
+    let size = LogicalSize::new(3.0, 4.0) * 2.0;
+    assert_eq!(size, LogicalSize::new(6.0, 8.0));
+    assert_eq!(
+        size / 4.0 + LogicalSize::new(0.5, 0.0),
+        LogicalSize::new(2.0, 2.0)
+    );
+
+    assert_eq!(prect(0, 0, 5, 5) + delta, prect(6, -5, 5, 5));
+}
+
+#[test]
+fn size_utilities() {
+    let size = PhysicalSize::new(1920u32, 1080);
+    assert_eq!(size.area(), 1920.0 * 1080.0);
+    assert_eq!(size.aspect_ratio(), Some(16.0 / 9.0));
+    assert_eq!(PhysicalSize::new(0u32, 10).aspect_ratio(), None);
+    assert_eq!(
+        size.clamp(PhysicalSize::new(640, 480), PhysicalSize::new(1280, 2000)),
+        PhysicalSize::new(1280, 1080)
+    );
+    assert_eq!(
+        size.scale_to_fit(PhysicalSize::new(800, 800)),
+        PhysicalSize::new(800, 450)
+    );
+    assert_eq!(
+        size.scale_to_fill(PhysicalSize::new(800, 800)),
+        PhysicalSize::new(1422, 800)
+    );
+
+    let logical = Size::from(LogicalSize::new(400.0, 300.0));
+    let bounds = Size::from(PhysicalSize::new(400u32, 400));
+    assert_eq!(
+        logical.scale_to_fit(bounds, 2.0),
+        Size::from(LogicalSize::new(200.0, 150.0))
+    );
+    assert_eq!(
+        logical.clamp(bounds, bounds, 2.0),
+        Size::from(LogicalSize::new(200.0, 200.0))
+    );
+}
+
+#[test]
+fn size_constraints() {
+    let constraints = SizeConstraints {
+        min_size: Some(LogicalSize::new(100.0, 100.0).into()),
+        max_size: Some(PhysicalSize::new(300u32, 300).into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        constraints.constrain(PhysicalSize::new(50u32, 500).into(), 2.0),
+        PhysicalSize::new(200, 300)
+    );
+
+    let constraints = SizeConstraints {
+        base_size: Some(PhysicalSize::new(10u32, 10).into()),
+        resize_increments: Some(PhysicalSize::new(8u32, 16).into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        constraints.constrain(PhysicalSize::new(50u32, 50).into(), 1.0),
+        PhysicalSize::new(50, 42)
+    );
+
+    let mut constraints = SizeConstraints {
+        min_aspect_ratio: Some(1.0),
+        max_aspect_ratio: Some(2.0),
+        ..Default::default()
+    };
+    assert_eq!(
+        constraints.constrain(PhysicalSize::new(100u32, 300).into(), 1.0),
+        PhysicalSize::new(100, 100)
+    );
+    assert_eq!(
+        constraints.constrain(PhysicalSize::new(500u32, 100).into(), 1.0),
+        PhysicalSize::new(200, 100)
+    );
+    // Shrinking the width would break the minimum size, so the height grows instead.
+    constraints.min_size = Some(PhysicalSize::new(400u32, 0).into());
+    assert_eq!(
+        constraints.constrain(PhysicalSize::new(500u32, 100).into(), 1.0),
+        PhysicalSize::new(500, 250)
+    );
+
+    // Degenerate ratios are ignored, and extreme ones can't overflow.
+    for &ratio in &[0.0, -1.0, f64::NAN, f64::INFINITY, f64::MIN_POSITIVE] {
+        let constraints = SizeConstraints {
+            min_aspect_ratio: Some(ratio),
+            max_aspect_ratio: Some(ratio),
+            ..Default::default()
+        };
+        let size = constraints.constrain(PhysicalSize::new(500u32, 100).into(), 1.0);
+        if ratio.is_finite() && ratio > 0.0 {
+            assert_eq!(size.width, 0);
+        } else {
+            assert_eq!(size, PhysicalSize::new(500, 100));
+        }
+    }
+}
+
+#[test]
+fn scale_factor_change() {
+    let change = size_for_scale_factor_change(PhysicalSize::new(801, 600), 1.0, 1.25, None);
+    assert_eq!(change.physical, PhysicalSize::new(1001, 750));
+    assert_eq!(change.logical, LogicalSize::new(801.0, 600.0));
+
+    let constraints = SizeConstraints {
+        max_size: Some(LogicalSize::new(700.0, 700.0).into()),
+        ..Default::default()
+    };
+    let change =
+        size_for_scale_factor_change(PhysicalSize::new(1600, 1200), 2.0, 1.0, Some(&constraints));
+    assert_eq!(change.physical, PhysicalSize::new(700, 600));
+    assert_eq!(change.logical, LogicalSize::new(800.0, 600.0));
+}
+
+#[test]
+fn scale_factor_from_monitor_mm() {
+    let twelfths = ScaleFactorSnapping::Twelfths;
+    // A 27" 4K monitor.
+    let dpi_factor = scale_factor_from_mm(PhysicalSize::new(3840, 2160), (597, 336), twelfths);
+    assert_eq!(dpi_factor.get(), 20.0 / 12.0);
+    assert_eq!(
+        scale_factor_from_mm(
+            PhysicalSize::new(3840, 2160),
+            (597, 336),
+            ScaleFactorSnapping::Integer
+        )
+        .get(),
+        2.0
+    );
+    // A 24" 1080p monitor would be slightly below 1.0.
+    let dpi_factor = scale_factor_from_mm(PhysicalSize::new(1920, 1080), (531, 299), twelfths);
+    assert_eq!(dpi_factor.get(), 1.0);
+
+    // Bogus sizes.
+    for &size_mm in &[(0, 0), (160, 90), (16, 10), (1, 1)] {
+        let dpi_factor = scale_factor_from_mm(PhysicalSize::new(3840, 2160), size_mm, twelfths);
+        assert_eq!(dpi_factor.get(), 1.0);
+    }
+}
+
+#[test]
+fn x11_scale_factor_precedence() {
+    use winit_types::dpi::{X11ScaleFactorRejection as Rejection, X11ScaleFactorSource as Source};
+
+    let randr = Some((PhysicalSize::new(3840, 2160), (597, 336)));
+    let mut inputs = X11ScaleFactorInputs {
+        env_var: Some("1.5"),
+        xft_dpi: Some(192.0),
+        randr,
+        xinerama: None,
+    };
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(
+        (resolved.scale_factor.get(), resolved.source),
+        (1.5, Source::EnvVar)
+    );
+
+    inputs.env_var = Some("");
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(
+        (resolved.scale_factor.get(), resolved.source),
+        (2.0, Source::XftDpi)
+    );
+    assert_eq!(resolved.rejected, vec![(Source::EnvVar, Rejection::NotSet)]);
+
+    inputs.env_var = Some("RandR");
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(resolved.source, Source::RandR);
+    assert_eq!(
+        resolved.rejected,
+        vec![
+            (Source::EnvVar, Rejection::DeferredToRandR),
+            (Source::XftDpi, Rejection::OverriddenByEnvVar),
+        ]
+    );
+
+    inputs.env_var = None;
+    inputs.xft_dpi = Some(0.0);
+    inputs.randr = None;
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(
+        (resolved.scale_factor.get(), resolved.source),
+        (1.0, Source::Default)
+    );
+    assert_eq!(
+        resolved.rejected[1],
+        (Source::XftDpi, Rejection::Invalid(0.0))
+    );
+    assert_eq!(resolved.rejected.len(), 4);
+
+    // A bogus RandR size falls through to Xinerama instead of giving `1.0`.
+    inputs.xft_dpi = None;
+    inputs.randr = Some((PhysicalSize::new(1920, 1080), (1, 1)));
+    inputs.xinerama = randr;
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(
+        (resolved.scale_factor.get(), resolved.source),
+        (20.0 / 12.0, Source::Xinerama)
+    );
+    assert_eq!(
+        resolved.rejected[2],
+        (Source::RandR, Rejection::BogusSize((1, 1)))
+    );
+
+    inputs.xinerama = Some((PhysicalSize::new(1920, 1080), (16, 9)));
+    let resolved = resolve_x11_scale_factor(&inputs).unwrap();
+    assert_eq!(
+        (resolved.scale_factor.get(), resolved.source),
+        (1.0, Source::Default)
+    );
+    assert_eq!(
+        resolved.rejected[3],
+        (Source::Xinerama, Rejection::BogusSize((16, 9)))
+    );
+}
+
+#[test]
+fn x11_scale_factor_bad_env_var() {
+    for &var in &["two", "0", "-1"] {
+        let inputs = X11ScaleFactorInputs {
+            env_var: Some(var),
+            ..Default::default()
+        };
+        assert!(resolve_x11_scale_factor(&inputs).is_err());
+    }
+}
+
+#[test]
+fn fractional_scale() {
+    assert!(FractionalScale::new(0).is_err());
+    assert!(FractionalScale::from_integer(0).is_err());
+    assert_eq!(FractionalScale::from_integer(2).unwrap().numerator(), 240);
+
+    let scale = FractionalScale::new(180).unwrap();
+    assert_eq!(scale.integer(), None);
+    assert_eq!(scale.ceil(), 2);
+    assert_eq!(ScaleFactor::from(scale).get(), 1.5);
+    assert_eq!(
+        scale.buffer_size(LogicalSize::new(100, 50)),
+        PhysicalSize::new(150, 75)
+    );
+    // 101 * 1.5 = 151.5, rounded away from zero.
+    assert_eq!(
+        scale.buffer_size(LogicalSize::new(101, 51)),
+        PhysicalSize::new(152, 77)
+    );
+    assert_eq!(
+        scale.viewport_destination(PhysicalSize::new(152, 77)),
+        LogicalSize::new(101, 51)
+    );
+    assert!(scale.surface_size(PhysicalSize::new(150, 75)).is_err());
+
+    for &numerator in &[120, 150, 162, 180, 210, 240, 300] {
+        let scale = FractionalScale::new(numerator).unwrap();
+        for width in 0..500 {
+            let size = LogicalSize::new(width, 1080 - width);
+            assert_eq!(scale.viewport_destination(scale.buffer_size(size)), size);
+        }
+    }
+}
+
+#[test]
+fn integer_buffer_scale() {
+    let scale = FractionalScale::from_integer(2).unwrap();
+    assert_eq!(scale.integer(), Some(2));
+    assert_eq!(scale.ceil(), 2);
+    assert_eq!(
+        scale.buffer_size(LogicalSize::new(101, 51)),
+        PhysicalSize::new(202, 102)
+    );
+    assert_eq!(
+        scale.surface_size(PhysicalSize::new(202, 102)).unwrap(),
+        LogicalSize::new(101, 51)
+    );
+    assert!(scale.surface_size(PhysicalSize::new(201, 102)).is_err());
+}
+
+#[test]
+fn rational_scale_factor() {
+    assert!(ScaleFactor::from_ratio(0, 1).is_err());
+    assert!(ScaleFactor::from_ratio(1, 0).is_err());
+    let dpi_factor = ScaleFactor::from_ratio(14, 12).unwrap();
+    assert_eq!(dpi_factor.ratio(), Some((7, 6)));
+    assert_eq!(dpi_factor.to_string(), "7/6");
+    assert_eq!(ScaleFactor::new(2.0).unwrap().ratio(), Some((2, 1)));
+    assert_eq!(ScaleFactor::new(1.1).unwrap().ratio(), None);
+    assert_eq!(
+        FractionalScale::new(150).unwrap().to_scale_factor().ratio(),
+        Some((5, 4))
+    );
+    assert_eq!(
+        ScaleFactor::from_ratio(3, 2).unwrap(),
+        ScaleFactor::from(1.5)
+    );
+
+    // Going through `7 / 6` as an `f64` gives 29.999999999999996, which floors to 29.
+    let physical = PhysicalPosition::new(35, 35);
+    assert_ne!(physical.to_logical::<f64>(7.0 / 6.0).x, 30.0);
+    assert_eq!(
+        physical.to_logical::<f64>(dpi_factor),
+        LogicalPosition::new(30.0, 30.0)
+    );
+    assert_eq!(
+        physical
+            .to_logical::<f64>(dpi_factor)
+            .cast_with::<i32>(Rounding::Floor),
+        LogicalPosition::new(30, 30)
+    );
+
+    for &(numerator, denominator) in &[(5, 4), (7, 4), (7, 6), (13, 12), (17, 12), (5, 3)] {
+        let dpi_factor = ScaleFactor::from_ratio(numerator, denominator).unwrap();
+        for x in -5000..5000 {
+            let physical = PhysicalPosition::new(x, -x);
+            let logical: LogicalPosition<f64> = physical.to_logical(dpi_factor);
+            assert_eq!(logical.to_physical::<i32>(dpi_factor), physical);
+        }
+    }
+}
+
+#[test]
+fn non_uniform_scale_factor() {
+    let dpi_factor = ScaleFactor2D::try_new(2.0, 1.5).unwrap();
+    assert!(!dpi_factor.is_uniform());
+    assert!(ScaleFactor2D::try_new(2.0, -1.0).is_err());
+    assert!(ScaleFactor2D::from(1.5).is_uniform());
+    assert_eq!(ScaleFactor2D::default(), ScaleFactor2D::IDENTITY);
+
+    let logical = LogicalPosition::new(10.0, 10.0);
+    let physical: PhysicalPosition<i32> = logical.to_physical(dpi_factor);
+    assert_eq!(physical, PhysicalPosition::new(20, 15));
+    assert_eq!(physical.to_logical::<f64>(dpi_factor), logical);
+
+    let size: PhysicalSize<u32> = LogicalSize::new(100.0, 100.0).to_physical(dpi_factor);
+    assert_eq!(size, PhysicalSize::new(200, 150));
+    assert_eq!(
+        Size::from(size).to_logical::<f64>(dpi_factor),
+        LogicalSize::new(100.0, 100.0)
+    );
+
+    let rect = LogicalRect::new(logical, LogicalSize::new(100.0, 100.0));
+    assert_eq!(
+        rect.to_physical::<i32>(dpi_factor),
+        PhysicalRect::new(PhysicalPosition::new(20, 15), PhysicalSize::new(200, 150))
+    );
+    assert_eq!(
+        Rect::from(rect).to_physical::<i32>(dpi_factor),
+        PhysicalRect::new(PhysicalPosition::new(20, 15), PhysicalSize::new(200, 150))
+    );
+    assert_eq!(
+        Position::from(logical).to_physical::<i32>(dpi_factor),
+        PhysicalPosition::new(20, 15)
+    );
+    assert_eq!(
+        LogicalPosition::new(0.4, 0.4).snap_to_pixels(dpi_factor),
+        LogicalPosition::new(0.5, 2.0 / 3.0)
+    );
+}
+
+/// Some geometry, rescaled as a whole through the conversion traits.
+struct Layout<T> {
+    anchor: Position,
+    items: Vec<T>,
+    clip: Option<LogicalRect<f64>>,
+}
+
+impl<T: ToPhysical<i32>> ToPhysical<i32> for Layout<T> {
+    type Output = (
+        PhysicalPosition<i32>,
+        Vec<T::Output>,
+        Option<PhysicalRect<i32>>,
+    );
+
+    fn to_physical(&self, dpi_factor: impl Into<ScaleFactor2D>) -> Self::Output {
+        let dpi_factor = dpi_factor.into();
+        (
+            ToPhysical::to_physical(&self.anchor, dpi_factor),
+            self.items.to_physical(dpi_factor),
+            ToPhysical::to_physical(&self.clip, dpi_factor),
+        )
+    }
+}
+
+fn physical_size<T: ToPhysical<u32, Output = PhysicalSize<u32>>>(size: T) -> PhysicalSize<u32> {
+    size.to_physical(2.0)
+}
+
+#[test]
+fn conversion_traits() {
+    assert_eq!(
+        physical_size(LogicalSize::new(100.0, 50.0)),
+        PhysicalSize::new(200, 100)
+    );
+    assert_eq!(
+        physical_size(PhysicalSize::new(100.0, 50.0)),
+        PhysicalSize::new(100, 50)
+    );
+    assert_eq!(
+        physical_size(Size::Logical(LogicalSize::new(100.0, 50.0))),
+        PhysicalSize::new(200, 100)
+    );
+
+    let layout = Layout {
+        anchor: LogicalPosition::new(10.0, 20.0).into(),
+        items: vec![LogicalDelta::new(1.0, 2.0), LogicalDelta::new(3.0, 4.0)],
+        clip: Some(LogicalRect::new(
+            LogicalPosition::new(0.0, 0.0),
+            LogicalSize::new(5.0, 5.0),
+        )),
+    };
+    let (anchor, items, clip) = layout.to_physical(2.0);
+    assert_eq!(anchor, PhysicalPosition::new(20, 40));
+    assert_eq!(
+        items,
+        vec![PhysicalDelta::new(2, 4), PhysicalDelta::new(6, 8)]
+    );
+    assert_eq!(
+        clip,
+        Some(PhysicalRect::new(
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(10, 10)
+        ))
+    );
+
+    let logical: LogicalPosition<f64> =
+        FromPhysical::from_physical(PhysicalPosition::new(3, 6), 1.5);
+    assert_eq!(logical, LogicalPosition::new(2.0, 4.0));
+    let physical: PhysicalRect<u32> = FromLogical::from_logical(
+        LogicalRect::new(LogicalPosition::new(2.0, 4.0), LogicalSize::new(2.0, 2.0)),
+        1.5,
+    );
+    assert_eq!(
+        physical,
+        PhysicalRect::new(PhysicalPosition::new(3, 6), PhysicalSize::new(3, 3))
+    );
+
+    // Lengths are scaled by the horizontal factor.
+    fn physical_length<T: ToPhysical<i32, Output = PhysicalUnit<i32>>>(
+        length: T,
+        dpi_factor: ScaleFactor2D,
+    ) -> PhysicalUnit<i32> {
+        length.to_physical(dpi_factor)
+    }
+    let dpi_factor = ScaleFactor2D::try_new(2.0, 3.0).unwrap();
+    assert_eq!(
+        physical_length(PixelUnit::from(LogicalUnit::new(5.0)), dpi_factor),
+        PhysicalUnit::new(10)
+    );
+    assert_eq!(
+        physical_length(PixelUnit::from(PhysicalUnit::new(5)), dpi_factor),
+        PhysicalUnit::new(5)
+    );
+    assert_eq!(
+        physical_length(LogicalUnit::new(5.0), dpi_factor),
+        PhysicalUnit::new(10)
+    );
+    let logical: LogicalUnit<f64> = FromPhysical::from_physical(PhysicalUnit::new(6), dpi_factor);
+    assert_eq!(logical, LogicalUnit::new(3.0));
+    assert_eq!(
+        ToLogical::<f64>::to_logical(&PixelUnit::from(PhysicalUnit::new(6)), 1.5),
+        LogicalUnit::new(4.0)
+    );
+}
+
+enum Texel {}
+
+impl Unit for Texel {
+    const NAME: &'static str = "Texel";
+}
+
+#[test]
+fn aliases() {
+    // The aliases are the unit-tagged types, so values can be passed to code written for either.
+    fn logical_position<P>(position: typed::Position<P, Logical>) -> LogicalPosition<P> {
+        position
+    }
+    fn physical_size<P>(size: PhysicalSize<P>) -> typed::Size<P, Physical> {
+        size
+    }
+    let _: typed::Delta<i32, Physical> = PhysicalDelta::new(1, 2);
+    let _: LogicalInsets<f64> = typed::Insets::<f64, Logical>::uniform(1.0);
+
+    // Everything but rectangles has a unit marker, which literals fill in and patterns skip.
+    let literal = LogicalSize {
+        width: 1.0,
+        height: 2.0,
+        unit: PhantomData,
+    };
+    assert_eq!(literal, LogicalSize::new(1.0, 2.0));
+    let LogicalPosition { x, y, .. } = logical_position(LogicalPosition::new(1.0, 2.0));
+    assert_eq!((x, y), (1.0, 2.0));
+    let PhysicalSize { width, height, .. } = physical_size(PhysicalSize::new(3u32, 4));
+    assert_eq!((width, height), (3, 4));
+    let PhysicalInsets { left, bottom, .. } = PhysicalInsets::new(1, 2, 3, 4);
+    assert_eq!((left, bottom), (1, 4));
+    let LogicalRect { pos, size } = LogicalRect {
+        pos: LogicalPosition::new(1.0, 2.0),
+        size: LogicalSize::new(3.0, 4.0),
+    };
+    assert_eq!(LogicalRect::new(pos, size), (1.0, 2.0, 3.0, 4.0).into());
+}
+
+#[test]
+fn typed_units() {
+    let texels_per_point: Scale<Logical, Texel> = Scale::new(4.0);
+    let texel: typed::Position<u32, Texel> =
+        texels_per_point.transform_position(LogicalPosition::new(1.5, 2.0));
+    assert_eq!(texel, typed::Position::new(6, 8));
+    let rect: typed::Rect<f64, Texel> = (0.0, 0.0, 8.0, 8.0).into();
+    assert!(!rect.contains(texel.cast()));
+    assert_eq!(
+        rect.intersection(&(6.0, 6.0, 4.0, 4.0).into()),
+        Some((6.0, 6.0, 2.0, 2.0).into())
+    );
+
+    let texels_to_physical = texels_per_point
+        .inverse()
+        .then(&Scale::<Logical, Physical>::new(
+            ScaleFactor::from_ratio(3, 2).unwrap(),
+        ));
+    assert_eq!(texels_to_physical.factor().x.ratio(), Some((3, 8)));
+    assert_eq!(
+        texels_to_physical.transform_size::<_, u32>(typed::Size::<u32, Texel>::new(16, 8)),
+        PhysicalSize::new(6, 3)
+    );
+
+    let to_physical: Scale<Logical, Physical> = Scale::new(1.5);
+    let size = LogicalSize::new(100.0, 50.0);
+    assert_eq!(
+        to_physical.transform_size::<_, u32>(size),
+        size.to_physical(1.5)
+    );
+    assert_eq!(
+        to_physical
+            .inverse()
+            .transform_size::<_, f64>(PhysicalSize::new(150, 75)),
+        size
+    );
+
+    let delta = texel.cast::<i32>() - typed::Position::new(2, 2);
+    assert_eq!(delta * 2, typed::Delta::new(8, 12));
+    assert_eq!(
+        format!("{:?}", PhysicalSize::new(1, 2)),
+        "PhysicalSize { width: 1, height: 2 }"
+    );
+    assert_eq!(format!("{:?}", delta), "TexelDelta { x: 4, y: 6 }");
+}
+
+#[test]
+fn pixel_units() {
+    let border = LogicalUnit::new(1.0);
+    assert_eq!(border.to_physical::<u32>(1.5), PhysicalUnit(2));
+    assert_eq!(
+        border.to_physical_with::<u32>(1.5, Rounding::Outward),
+        PhysicalUnit(2)
+    );
+    assert_eq!(
+        border.to_physical_with::<u32>(1.25, Rounding::Floor),
+        PhysicalUnit(1)
+    );
+    assert_eq!(border.snap_to_pixels(1.5), LogicalUnit(4.0 / 3.0));
+    assert!(border.try_to_physical::<u32>(0.0).is_err());
+
+    let scroll = PhysicalUnit::new(-30);
+    assert_eq!(scroll.to_logical::<f64>(1.5), LogicalUnit(-20.0));
+    assert_eq!(
+        LogicalUnit::<f64>::from_physical(scroll, ScaleFactor::from_ratio(3, 2).unwrap()),
+        LogicalUnit(-20.0)
+    );
+    assert_eq!(
+        PhysicalUnit(300.0).saturating_cast::<u8>(),
+        PhysicalUnit(255)
+    );
+    assert!(PhysicalUnit(f64::NAN).try_cast::<i32>().is_err());
+    assert!(LogicalUnit(2.0) > LogicalUnit(1.5));
+
+    let unit = PixelUnit::new(LogicalUnit(10.0));
+    assert_eq!(unit.to_physical::<i32>(2.0), PhysicalUnit(20));
+    assert_eq!(unit.to_logical::<f64>(2.0), LogicalUnit(10.0));
+    let unit = PixelUnit::from(PhysicalUnit(15u32));
+    assert_eq!(unit, PixelUnit::Physical(PhysicalUnit(15)));
+    assert_eq!(unit.to_logical::<f64>(1.5), LogicalUnit(10.0));
+}
+
+#[test]
+fn insets() {
+    let frame = PhysicalInsets::new(2, 30, 2, 2);
+    let outer = PhysicalSize::new(804, 632);
+    let inner = outer.inset(frame);
+    assert_eq!(inner, PhysicalSize::new(800, 600));
+    assert_eq!(inner.outset(frame), outer);
+    assert_eq!(frame.size(), PhysicalSize::new(4, 32));
+    assert_eq!(
+        PhysicalSize::new(3, 40).inset(frame),
+        PhysicalSize::new(0, 8)
+    );
+
+    let outer = prect(100, 100, 804, 632);
+    let inner = outer.inset(frame);
+    assert_eq!(inner, prect(102, 130, 800, 600));
+    assert_eq!(inner.outset(frame), outer);
+    assert_eq!(
+        prect(0, 0, 3, 3).inset(PhysicalInsets::uniform(2)),
+        prect(2, 2, 0, 0)
+    );
+
+    let safe_area = LogicalInsets::new(0.0, 44.0, 0.0, 34.0);
+    assert_eq!(
+        safe_area.to_physical::<u32>(3.0),
+        PhysicalInsets::new(0, 132, 0, 102)
+    );
+    assert_eq!(
+        LogicalInsets::symmetric(0.5, 1.0).to_physical_with::<u32>(1.5, Rounding::Outward),
+        PhysicalInsets::new(1, 2, 1, 2)
+    );
+    assert_eq!(
+        frame.to_logical::<f64>(ScaleFactor2D::try_new(2.0, 1.0).unwrap()),
+        LogicalInsets::new(1.0, 30.0, 1.0, 2.0)
+    );
+    let screen = LogicalRect::new(
+        LogicalPosition::new(0.0, 0.0),
+        LogicalSize::new(390.0, 844.0),
+    );
+    assert_eq!(
+        screen.inset(safe_area),
+        LogicalRect::new(
+            LogicalPosition::new(0.0, 44.0),
+            LogicalSize::new(390.0, 766.0)
+        )
+    );
+
+    assert_eq!(
+        Scale::<Logical, Physical>::new(3.0).transform_insets::<_, u32>(safe_area),
+        safe_area.to_physical(3.0)
+    );
+
+    let insets = Insets::new(safe_area);
+    assert_eq!(
+        insets.inset_size(Size::Physical(PhysicalSize::new(1170, 2532)), 3.0),
+        Size::Physical(PhysicalSize::new(1170, 2298))
+    );
+    assert_eq!(
+        insets.outset_rect(Rect::Logical(screen.inset(safe_area)), 3.0),
+        Rect::Logical(screen)
+    );
+    assert_eq!(
+        ToLogical::<f64>::to_logical(&Insets::from(frame), 2.0),
+        LogicalInsets::new(1.0, 15.0, 1.0, 1.0)
+    );
+}
+
+#[test]
+fn transforms() {
+    let surface = PhysicalSize::new(100, 50);
+    let rect = prect(10, 5, 20, 10);
+    let expected = [
+        (Transform::Normal, prect(10, 5, 20, 10)),
+        (Transform::Rotate90, prect(35, 10, 10, 20)),
+        (Transform::Rotate180, prect(70, 35, 20, 10)),
+        (Transform::Rotate270, prect(5, 70, 10, 20)),
+        (Transform::Flipped, prect(70, 5, 20, 10)),
+        (Transform::Flipped90, prect(5, 10, 10, 20)),
+        (Transform::Flipped180, prect(10, 35, 20, 10)),
+        (Transform::Flipped270, prect(35, 70, 10, 20)),
+    ];
+    for &(transform, transformed) in &expected {
+        assert_eq!(transform.transform_rect(rect, surface), transformed);
+        let size = transform.transform_size(surface);
+        assert_eq!(size.width == 50, transform.swaps_axes());
+        let inverse = transform.inverse().unwrap();
+        assert_eq!(inverse.transform_rect(transformed, size), rect);
+        assert_eq!(transform.then(inverse, surface), Transform::Normal);
+    }
+
+    assert_eq!(
+        Transform::Rotate90.transform_position(PhysicalPosition::new(0, 0), surface),
+        PhysicalPosition::new(50, 0)
+    );
+    // Clockwise from surface to buffer space, so the right edge ends up at the bottom.
+    assert_eq!(
+        Transform::Rotate90.transform_position(PhysicalPosition::new(100, 0), surface),
+        PhysicalPosition::new(50, 100)
+    );
+    assert_eq!(
+        Transform::Rotate270
+            .transform_position(PhysicalPosition::new(50, 100), PhysicalSize::new(50, 100)),
+        PhysicalPosition::new(100, 0)
+    );
+    assert_eq!(
+        Transform::Rotate90.then(Transform::Rotate90, surface),
+        Transform::Rotate180
+    );
+    assert_eq!(
+        Transform::Rotate90.then(Transform::Flipped, surface),
+        Transform::Flipped90
+    );
+    assert_eq!(Transform::Rotate90.inverse(), Some(Transform::Rotate270));
+
+    let transform =
+        Transform::Rotate90.then(AffineTransform::translation(10.0, 20.0).into(), surface);
+    assert_eq!(
+        transform.transform_rect(rect, surface),
+        prect(45, 30, 10, 20)
+    );
+    assert_eq!(
+        transform
+            .inverse()
+            .unwrap()
+            .transform_rect(prect(45, 30, 10, 20), surface),
+        rect
+    );
+    // Affine results that are plain rotations or flips collapse back into them.
+    assert_eq!(
+        transform.then(transform.inverse().unwrap(), surface),
+        Transform::Normal
+    );
+    assert_eq!(
+        Transform::from(AffineTransform::rotation(std::f64::consts::PI))
+            .then(AffineTransform::translation(100.0, 50.0).into(), surface),
+        Transform::Rotate180
+    );
+    assert_eq!(
+        Transform::from(AffineTransform::IDENTITY).inverse(),
+        Some(Transform::Normal)
+    );
+
+    let scale = Transform::from(AffineTransform::scale(0.5, 0.5));
+    assert_eq!(
+        scale.transform_rect(prect(1, 1, 3, 3), surface),
+        prect(0, 0, 2, 2)
+    );
+    assert_eq!(
+        scale.transform_size(PhysicalSize::new(101, 50)),
+        PhysicalSize::new(51, 25)
+    );
+    assert_eq!(
+        Transform::from(AffineTransform::scale(0.0, 1.0)).inverse(),
+        None
+    );
+
+    let rotation = AffineTransform::rotation(std::f64::consts::FRAC_PI_2);
+    let (x, y) = rotation.transform_point(1.0, 0.0);
+    assert!(x.abs() < 1e-12 && (y - 1.0).abs() < 1e-12);
+    let (x, y) = rotation
+        .then(&rotation.inverse().unwrap())
+        .transform_point(3.0, 4.0);
+    assert!((x - 3.0).abs() < 1e-12 && (y - 4.0).abs() < 1e-12);
+}
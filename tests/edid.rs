@@ -0,0 +1,92 @@
+use winit_types::dpi::PhysicalSize;
+use winit_types::edid::Edid;
+use winit_types::error::ErrorType;
+use winit_types::monitor::VideoMode;
+
+/// A 24" 1080p monitor with a CEA-861 extension block.
+const EDID: [u8; 256] = [
+    0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x10, 0xAC, 0xC4, 0xA0, 0x4C, 0x4A, 0x32, 0x34,
+    0x2C, 0x17, 0x01, 0x04, 0xA5, 0x35, 0x1E, 0x78, 0x3A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40, 0x58, 0x2C,
+    0x45, 0x00, 0x0F, 0x28, 0x21, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x00, 0xFD, 0x00, 0x38, 0x4C, 0x1E,
+    0x53, 0x11, 0x00, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x44,
+    0x45, 0x4C, 0x4C, 0x20, 0x55, 0x32, 0x34, 0x31, 0x34, 0x48, 0x0A, 0x20, 0x00, 0x00, 0x00, 0xFF,
+    0x00, 0x41, 0x42, 0x43, 0x31, 0x32, 0x33, 0x34, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x01, 0xF9,
+    // CEA-861 extension block.
+    0x02, 0x03, 0x08, 0xF1, 0x43, 0x90, 0x04, 0x03, 0x01, 0x1D, 0x00, 0x72, 0x51, 0xD0, 0x1E, 0x20,
+    0x6E, 0x28, 0x55, 0x00, 0x0F, 0x28, 0x21, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xD8,
+];
+
+#[test]
+fn edid_base_block() {
+    let edid = Edid::parse(&EDID).unwrap();
+    assert_eq!(edid.manufacturer, "DEL");
+    assert_eq!(edid.product_code, 0xA0C4);
+    assert_eq!(edid.serial_number, Some(0x3432_4A4C));
+    assert_eq!(edid.serial.as_deref(), Some("ABC1234"));
+    assert_eq!(edid.product_name.as_deref(), Some("DELL U2414H"));
+    assert_eq!(edid.version, (1, 4));
+    // Taken from the preferred timing rather than the centimetre precision base block.
+    assert_eq!(edid.size_mm, Some((527, 296)));
+
+    let preferred = edid.preferred_timing().unwrap();
+    assert_eq!(
+        preferred.mode,
+        VideoMode::new(PhysicalSize::new(1920, 1080), 60_000)
+    );
+    assert_eq!(preferred.pixel_clock_khz, 148_500);
+    assert_eq!(preferred.blanking, PhysicalSize::new(280, 45));
+    assert!(!preferred.interlaced);
+}
+
+#[test]
+fn edid_cea_extension() {
+    let edid = Edid::parse(&EDID).unwrap();
+    let cea = edid.cea.as_ref().unwrap();
+    assert_eq!(cea.revision, 3);
+    assert!(cea.underscan);
+    assert!(cea.basic_audio);
+    assert_eq!(cea.video_codes, vec![16, 4, 3]);
+
+    assert_eq!(edid.detailed_timings.len(), 2);
+    assert_eq!(
+        edid.detailed_timings[1].mode,
+        VideoMode::new(PhysicalSize::new(1280, 720), 60_000)
+    );
+
+    // Drivers sometimes only expose the base block.
+    let edid = Edid::parse(&EDID[..128]).unwrap();
+    assert_eq!(edid.cea, None);
+    assert_eq!(edid.detailed_timings.len(), 1);
+}
+
+#[test]
+fn edid_corrupt() {
+    let mut corrupt = EDID;
+    corrupt[200] = 0xFF;
+    match Edid::parse(&corrupt).unwrap_err().ty {
+        ErrorType::EdidChecksumMismatch { block: 1 } => (),
+        ty => panic!("unexpected error: {:?}", ty),
+    }
+
+    let mut corrupt = EDID;
+    corrupt[21] = 0;
+    match Edid::parse(&corrupt).unwrap_err().ty {
+        ErrorType::EdidChecksumMismatch { block: 0 } => (),
+        ty => panic!("unexpected error: {:?}", ty),
+    }
+
+    for blob in &[&EDID[..100], &EDID[1..129]] {
+        match Edid::parse(blob).unwrap_err().ty {
+            ErrorType::InvalidEdid(_) => (),
+            ty => panic!("unexpected error: {:?}", ty),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use winit_types::dpi::{
+    LogicalPosition, PhysicalPosition, PhysicalRect, PhysicalSize, ScaleFactor,
+};
+use winit_types::monitor::{DesktopLayout, MonitorInfo};
+
+fn monitor(x: i32, y: i32, width: u32, height: u32, scale_factor: f64) -> MonitorInfo {
+    MonitorInfo {
+        name: None,
+        position: PhysicalPosition::new(x, y),
+        size: PhysicalSize::new(width, height),
+        scale_factor: ScaleFactor::new(scale_factor).unwrap(),
+        size_mm: None,
+    }
+}
+
+fn layout() -> DesktopLayout {
+    // A 4K monitor at 2x, with a 1080p monitor at 1x to its right, slightly lower and separated by
+    // a gap.
+    DesktopLayout::new(vec![
+        monitor(0, 0, 3840, 2160, 2.0),
+        monitor(3940, 200, 1920, 1080, 1.0),
+    ])
+}
+
+#[test]
+fn monitor_lookup() {
+    let layout = layout();
+    assert_eq!(
+        layout.bounds(),
+        Some(PhysicalRect::new(
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(5860, 2160)
+        ))
+    );
+    assert_eq!(
+        layout.monitor_at(PhysicalPosition::new(4000, 300)),
+        Some(&layout.monitors()[1])
+    );
+    assert_eq!(layout.monitor_at(PhysicalPosition::new(3900, 300)), None);
+    assert_eq!(
+        layout.nearest_monitor(PhysicalPosition::new(3900, 300)),
+        Some(&layout.monitors()[1])
+    );
+
+    let window = PhysicalRect::new(
+        PhysicalPosition::new(3500, 500),
+        PhysicalSize::new(800, 600),
+    );
+    assert_eq!(layout.monitor_for_rect(window), Some(&layout.monitors()[1]));
+}
+
+#[test]
+fn local_coordinates() {
+    let layout = layout();
+    let (monitor, local) = layout.to_local(PhysicalPosition::new(200, 100)).unwrap();
+    assert_eq!(monitor, &layout.monitors()[0]);
+    assert_eq!(local, LogicalPosition::new(100.0, 50.0));
+    assert_eq!(monitor.to_global(local), PhysicalPosition::new(200, 100));
+
+    let (monitor, local) = layout.to_local(PhysicalPosition::new(3900, 300)).unwrap();
+    assert_eq!(monitor, &layout.monitors()[1]);
+    assert_eq!(local, LogicalPosition::new(-40.0, 100.0));
+}
+
+#[test]
+fn extreme_coordinates() {
+    let layout = DesktopLayout::new(vec![
+        monitor(i32::MIN, i32::MIN, 100, 100, 1.0),
+        monitor(i32::MAX - 100, i32::MAX - 100, 100, 100, 2.0),
+    ]);
+    let (far, near) = (&layout.monitors()[0], &layout.monitors()[1]);
+    let corner = PhysicalPosition::new(i32::MAX, i32::MAX);
+    assert_eq!(
+        far.to_local(corner),
+        LogicalPosition::new(u32::MAX as f64, u32::MAX as f64)
+    );
+    assert_eq!(far.to_global(far.to_local(corner)), corner);
+    assert_eq!(
+        near.to_global(LogicalPosition::new(1e10, -1e10)),
+        PhysicalPosition::new(i32::MAX, i32::MIN)
+    );
+    assert_eq!(layout.nearest_monitor(corner), Some(near));
+    assert_eq!(
+        layout.monitor_for_rect(PhysicalRect::new(
+            PhysicalPosition::new(i32::MAX - 10, 0),
+            PhysicalSize::new(i32::MAX, 10),
+        )),
+        Some(near)
+    );
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::prect;
+use winit_types::dpi::{Origin, PhysicalSize};
+use winit_types::region::Region;
+
+#[test]
+fn coalesce_merges_only_exact_unions() {
+    let mut region: Region = vec![
+        prect(0, 0, 10, 10),
+        prect(10, 0, 10, 10),
+        prect(2, 2, 3, 3),
+        prect(0, 20, 5, 5),
+        prect(0, 0, 0, 5),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(region.len(), 4);
+
+    region.coalesce();
+    let mut rects = region.rects().to_vec();
+    rects.sort_by_key(|r| (r.pos.y, r.pos.x));
+    assert_eq!(rects, vec![prect(0, 0, 20, 10), prect(0, 20, 5, 5)]);
+}
+
+#[test]
+fn clamp_and_bounding_box() {
+    let mut region: Region = vec![
+        prect(-5, -5, 10, 10),
+        prect(90, 90, 20, 20),
+        prect(200, 0, 1, 1),
+    ]
+    .into_iter()
+    .collect();
+    region.clamp(PhysicalSize::new(100, 100));
+    assert_eq!(region.rects(), &[prect(0, 0, 5, 5), prect(90, 90, 10, 10)]);
+    assert_eq!(region.bounding_box(), Some(prect(0, 0, 100, 100)));
+    assert_eq!(Region::new().bounding_box(), None);
+}
+
+#[test]
+fn limit_merges_cheapest_pairs() {
+    let mut region: Region = vec![
+        prect(0, 0, 10, 10),
+        prect(12, 0, 10, 10),
+        prect(100, 100, 10, 10),
+    ]
+    .into_iter()
+    .collect();
+    region.limit(2);
+    assert_eq!(region.len(), 2);
+    assert!(region.rects().contains(&prect(0, 0, 22, 10)));
+    assert!(region.rects().contains(&prect(100, 100, 10, 10)));
+
+    region.limit(0);
+    assert_eq!(region.rects(), &[prect(0, 0, 110, 110)]);
+}
+
+#[test]
+fn flatten_for_egl() {
+    let region: Region = vec![prect(0, 0, 10, 5), prect(20, 90, 5, 10)]
+        .into_iter()
+        .collect();
+    let surface = PhysicalSize::new(100, 100);
+    assert_eq!(
+        region.flatten(Origin::TopLeft, surface),
+        vec![0, 0, 10, 5, 20, 90, 5, 10]
+    );
+    assert_eq!(
+        region.flatten(Origin::BottomLeft, surface),
+        vec![0, 95, 10, 5, 20, 0, 5, 10]
+    );
+}
@@ -1,7 +1,15 @@
 #![cfg(feature = "serde_feature")]
 
 use serde::{Deserialize, Serialize};
-use winit_types::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+use winit_types::dpi::{
+    AffineTransform, FractionalScale, Insets, LogicalDelta, LogicalInsets, LogicalPosition,
+    LogicalRect, LogicalSize, LogicalUnit, Origin, PhysicalDelta, PhysicalInsets, PhysicalPosition,
+    PhysicalRect, PhysicalSize, PhysicalUnit, PixelUnit, Rect, Rounding, ScaleFactor,
+    ScaleFactor2D, ScaleFactorChangeSize, ScaleFactorSnapping, SizeConstraints, Transform,
+};
+use winit_types::edid::{CeaExtension, DetailedTiming, Edid};
+use winit_types::monitor::{DesktopLayout, MonitorInfo, VideoMode};
+use winit_types::region::Region;
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
@@ -13,4 +21,44 @@ fn dpi_serde() {
     needs_serde::<PhysicalPosition<f64>>();
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
+    needs_serde::<LogicalDelta<f64>>();
+    needs_serde::<PhysicalDelta<i32>>();
+    needs_serde::<LogicalRect<f64>>();
+    needs_serde::<PhysicalRect<i32>>();
+    needs_serde::<LogicalUnit<f64>>();
+    needs_serde::<PhysicalUnit<i32>>();
+    needs_serde::<PixelUnit>();
+    needs_serde::<LogicalInsets<f64>>();
+    needs_serde::<PhysicalInsets<u32>>();
+    needs_serde::<Insets>();
+    needs_serde::<AffineTransform>();
+    needs_serde::<Transform>();
+    needs_serde::<Rect>();
+    needs_serde::<Origin>();
+    needs_serde::<ScaleFactor>();
+    needs_serde::<ScaleFactor2D>();
+    needs_serde::<FractionalScale>();
+    needs_serde::<ScaleFactorSnapping>();
+    needs_serde::<Rounding>();
+    needs_serde::<SizeConstraints>();
+    needs_serde::<ScaleFactorChangeSize>();
+}
+
+#[test]
+fn region_serde() {
+    needs_serde::<Region>();
+}
+
+#[test]
+fn edid_serde() {
+    needs_serde::<Edid>();
+    needs_serde::<DetailedTiming>();
+    needs_serde::<CeaExtension>();
+}
+
+#[test]
+fn monitor_serde() {
+    needs_serde::<MonitorInfo>();
+    needs_serde::<DesktopLayout>();
+    needs_serde::<VideoMode>();
 }
@@ -0,0 +1,77 @@
+use winit_types::platform::xresources::XResources;
+
+const RESOURCE_MANAGER: &str = "\
+! Set by the desktop environment
+Xft.dpi:\t144
+Xft.antialias:\t1
+Xcursor.theme: Adwaita
+Xcursor.size:\\
+  32
+xterm.title: line\\
+  continued
+*background: black
+xterm*background: white
+xterm.vt100.background: gray
+XTerm*?.foreground: green
+*Foreground: red
+emacs.title: \\ leading space\\nand a \\101\\\\
+emacs.font: Fira Sans,caf\\303\\251
+";
+
+#[test]
+fn well_known_resources() {
+    let resources = XResources::parse(RESOURCE_MANAGER);
+    assert_eq!(resources.xft_dpi(), Some(144.0));
+    assert_eq!(resources.xcursor_size(), Some(32));
+    assert_eq!(resources.xcursor_theme(), Some("Adwaita"));
+    assert_eq!(
+        resources.get("xterm.title", "XTerm.Title"),
+        Some("line  continued")
+    );
+    assert_eq!(XResources::parse("").xft_dpi(), None);
+}
+
+#[test]
+fn precedence() {
+    let resources = XResources::parse(RESOURCE_MANAGER);
+    assert_eq!(
+        resources.get("xterm.vt100.background", "XTerm.VT100.Background"),
+        Some("gray")
+    );
+    assert_eq!(
+        resources.get("xterm.menu.background", "XTerm.Menu.Background"),
+        Some("white")
+    );
+    assert_eq!(
+        resources.get("emacs.frame.background", "Emacs.Frame.Background"),
+        Some("black")
+    );
+    // `?` matches a level, which beats the loose `*Foreground` skipping it, even though the latter
+    // matches the last level by name.
+    assert_eq!(
+        resources.get("xterm.vt100.foreground", "XTerm.VT100.Foreground"),
+        Some("green")
+    );
+    assert_eq!(
+        resources.get("emacs.foreground", "Emacs.Foreground"),
+        Some("red")
+    );
+    assert_eq!(resources.get("xterm.background", "XTerm"), None);
+}
+
+#[test]
+fn escapes_and_overrides() {
+    let resources = XResources::parse(RESOURCE_MANAGER);
+    assert_eq!(
+        resources.get("emacs.title", "Emacs.Title"),
+        Some(" leading space\nand a A\\")
+    );
+    // xrdb escapes non-ASCII bytes, which have to be decoded as UTF-8 afterwards.
+    assert_eq!(
+        resources.get("emacs.font", "Emacs.Font"),
+        Some("Fira Sans,caf\u{e9}")
+    );
+
+    let resources = XResources::parse("Xft.dpi: 96\nmalformed line\n*.: 1\nXft.dpi: 120");
+    assert_eq!(resources.xft_dpi(), Some(120.0));
+}